@@ -0,0 +1,86 @@
+// cargo bench --bench contains
+use std::hint::black_box;
+use std::time::Instant;
+use wordle_rust::*;
+
+fn main() {
+    bench_contains(&parse_words(DEFAULT_WORDS));
+}
+
+// letter presence filtering with the per letter Vec<bool> index of bits against one
+// u32 mask per word and against scanning the words with contains
+fn bench_contains(words: &Words) {
+    let mut word_contains: [Vec<bool>; NUM_CHARS] = Default::default();
+    for w in words {
+        for (i, l) in ASCII_LOWER.iter().enumerate() {
+            word_contains[i].push(w.contains(l));
+        }
+    }
+    let masks: Vec<u32> = words.iter().map(letter_mask).collect();
+
+    // letters required and letters excluded by the feedback of some guess/answer pairs
+    let queries: Vec<(u32, u32)> = (0..2000)
+        .map(|i| {
+            let facts = check(
+                &words[i * 7 % words.len()],
+                &words[i * 13 % words.len()],
+                DuplicateMode::Standard,
+            );
+            let mask = |keep: fn(&Feedback) -> bool| {
+                facts
+                    .iter()
+                    .filter(|f| keep(&f.feedback))
+                    .filter_map(|f| letter_index(f.letter))
+                    .fold(0u32, |m, i| m | 1 << i)
+            };
+            let with = mask(|f| *f != Feedback::NotUsed);
+            (with, mask(|f| *f == Feedback::NotUsed) & !with)
+        })
+        .collect();
+
+    let t = Instant::now();
+    let naive: Vec<usize> = queries
+        .iter()
+        .map(|&(with, without)| {
+            words
+                .iter()
+                .filter(|w| {
+                    (0..NUM_CHARS).all(|i| {
+                        let has = w.contains(&ASCII_LOWER[i]);
+                        (with >> i & 1 == 0 || has) && (without >> i & 1 == 0 || !has)
+                    })
+                })
+                .count()
+        })
+        .collect();
+    println!("contains: {:.2?}", t.elapsed());
+
+    let t = Instant::now();
+    let bools: Vec<usize> = queries
+        .iter()
+        .map(|&(with, without)| {
+            (0..words.len())
+                .filter(|&j| {
+                    (0..NUM_CHARS).all(|i| {
+                        let has = word_contains[i][j];
+                        (with >> i & 1 == 0 || has) && (without >> i & 1 == 0 || !has)
+                    })
+                })
+                .count()
+        })
+        .collect();
+    println!("Vec<bool>: {:.2?}", t.elapsed());
+
+    let t = Instant::now();
+    let packed: Vec<usize> = queries
+        .iter()
+        .map(|&(with, without)| {
+            masks
+                .iter()
+                .filter(|&&m| m & with == with && m & without == 0)
+                .count()
+        })
+        .collect();
+    println!("u32 masks: {:.2?}", t.elapsed());
+    black_box((naive, bools, packed));
+}
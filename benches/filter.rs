@@ -0,0 +1,53 @@
+// cargo bench --bench filter
+use std::hint::black_box;
+use std::time::Instant;
+use wordle_rust::*;
+
+fn main() {
+    bench_filter(&parse_words(DEFAULT_WORDS));
+}
+
+// times filtering with letter counts computed per word against the precomputed table,
+// and against the Standard mode constraints
+fn bench_filter(words: &Words) {
+    let t = Instant::now();
+    let counts = count_letters(words);
+    println!("count_letters: {:.2?}", t.elapsed());
+
+    let fact_sets: Vec<Facts> = (0..200)
+        .map(|i| {
+            check(
+                &words[i * 7 % words.len()],
+                &words[i * 13 % words.len()],
+                DuplicateMode::Standard,
+            )
+        })
+        .collect();
+    let t = Instant::now();
+    black_box(
+        fact_sets
+            .iter()
+            .map(|f| filter_words(words, f, DuplicateMode::Standard))
+            .collect::<Vec<Words>>(),
+    );
+    println!("filter_words: {:.2?}", t.elapsed());
+    let t = Instant::now();
+    black_box(
+        fact_sets
+            .iter()
+            .map(|f| filter_words_counted(words, &counts, f, DuplicateMode::Standard))
+            .collect::<Vec<Words>>(),
+    );
+    println!("filter_words_counted: {:.2?}", t.elapsed());
+    let t = Instant::now();
+    black_box(
+        fact_sets
+            .iter()
+            .map(|f| {
+                let c = constraints_from_facts(f);
+                words.iter().filter(|w| c.matches(w)).copied().collect()
+            })
+            .collect::<Vec<Words>>(),
+    );
+    println!("constraints_from_facts: {:.2?}", t.elapsed());
+}
@@ -0,0 +1,32 @@
+// cargo bench --bench load
+use std::env;
+use std::fs;
+use std::hint::black_box;
+use std::io;
+use std::time::Instant;
+use wordle_rust::*;
+
+fn main() -> io::Result<()> {
+    bench_load(&parse_words(DEFAULT_WORDS))
+}
+
+// times loading the list from text against the binary format, both written to the
+// temp directory first and removed after
+fn bench_load(words: &Words) -> io::Result<()> {
+    let dir = env::temp_dir();
+    let text_path = dir.join("wordle-bench-load.txt");
+    let bin_path = dir.join("wordle-bench-load.bin");
+    let (text_path, bin_path) = (text_path.to_string_lossy(), bin_path.to_string_lossy());
+    let text: String = words.iter().map(|w| word_str(w) + "\n").collect();
+    fs::write(&*text_path, text)?;
+    save_words_bin(words, &bin_path)?;
+
+    let t = Instant::now();
+    black_box(load_words(&text_path)?);
+    println!("text: {:.2?}", t.elapsed());
+    let t = Instant::now();
+    black_box(load_words_bin(&bin_path)?);
+    println!("binary: {:.2?}", t.elapsed());
+    fs::remove_file(&*text_path)?;
+    fs::remove_file(&*bin_path)
+}
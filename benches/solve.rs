@@ -0,0 +1,35 @@
+// cargo bench --bench solve
+use std::hint::black_box;
+use std::time::Instant;
+use wordle_rust::*;
+
+// answers solve plays exhaustively, it's far too slow for the whole list
+const SOLVE_WORDS: usize = 30;
+
+fn main() {
+    let words = parse_words(DEFAULT_WORDS);
+    bench_solve(&words[..SOLVE_WORDS].to_vec());
+    bench_solve_all(&words);
+}
+
+// the exhaustive search re-filtering facts against the shared pattern table
+fn bench_solve(words: &Words) {
+    let t = Instant::now();
+    black_box(solve(words, words));
+    println!("solve: {:.2?}", t.elapsed());
+    let t = Instant::now();
+    black_box(solve_shared(words, words));
+    println!("solve_shared: {:.2?}", t.elapsed());
+}
+
+// the strategy's play over every answer, one at a time and in parallel
+fn bench_solve_all(words: &Words) {
+    let strategy = Strategy::default();
+    let opener = select_guess(words, words, strategy).unwrap();
+    let t = Instant::now();
+    black_box(solve_all(words, words, &opener, strategy));
+    println!("solve_all: {:.2?}", t.elapsed());
+    let t = Instant::now();
+    black_box(solve_all_par(words, words, &opener, strategy));
+    println!("solve_all_par: {:.2?}", t.elapsed());
+}
@@ -0,0 +1,5404 @@
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+#[cfg(not(feature = "parallel"))]
+use sequential::*;
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::convert::TryInto;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::hash::Hash;
+use std::io;
+use std::mem;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// stand-ins for the few rayon methods used, running on the calling thread for targets
+// without threads such as wasm32-unknown-unknown
+#[cfg(not(feature = "parallel"))]
+mod sequential {
+    pub trait ParIter<T> {
+        fn par_iter(&self) -> std::slice::Iter<'_, T>;
+    }
+
+    impl<T> ParIter<T> for [T] {
+        fn par_iter(&self) -> std::slice::Iter<'_, T> {
+            self.iter()
+        }
+    }
+
+    pub trait SequentialIterator: Iterator + Sized {
+        fn flat_map_iter<U, F>(self, f: F) -> std::iter::FlatMap<Self, U, F>
+        where
+            U: IntoIterator,
+            F: FnMut(Self::Item) -> U,
+        {
+            self.flat_map(f)
+        }
+
+        fn reduce_with(
+            self,
+            f: impl FnMut(Self::Item, Self::Item) -> Self::Item,
+        ) -> Option<Self::Item> {
+            self.reduce(f)
+        }
+    }
+
+    impl<I: Iterator> SequentialIterator for I {}
+}
+
+pub const NUM_CHARS: usize = 26;
+pub const WORD_LENGTH: usize = 5;
+pub const NUM_PATTERNS: usize = 243; // 3^WORD_LENGTH
+pub static ASCII_LOWER: [char; NUM_CHARS] = [
+    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's',
+    't', 'u', 'v', 'w', 'x', 'y', 'z',
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Feedback {
+    Correct,
+    Used,
+    NotUsed,
+    // the player isn't sure of the tile's color, imposes no constraint
+    Unknown,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Fact {
+    pub letter: char,
+    // the tile the feedback was given for, None when it isn't tied to one (e.g. an
+    // absent letter entered by hand); filtering never reads it for NotUsed facts
+    pub position: Option<usize>,
+    pub feedback: Feedback,
+}
+
+pub type Word = [char; WORD_LENGTH];
+
+// a fixed non-letter tile in phrase puzzles, e.g. "ab_de". Every word of a list has it
+// in the same places and it's always revealed, so check colors it green and the
+// letter tables skip it
+pub const SEPARATOR: char = '_';
+pub type Words = Vec<Word>;
+pub type Facts = Vec<Fact>;
+pub type Pattern = u8;
+pub type Placements = Vec<(char, usize)>;
+
+fn build_fact(f: Feedback, l: char, p: usize) -> Fact {
+    Fact {
+        letter: l,
+        position: Some(p),
+        feedback: f,
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GuessResult {
+    pub guess: Word,
+    pub guesses: usize,
+    pub num_candidates: usize,
+    // number of guesses each candidate takes when following this result's policy
+    pub depths: Vec<(Word, usize)>,
+    // candidates left under each pattern the guess can show, smallest first
+    pub partition_sizes: Vec<usize>,
+}
+
+impl GuessResult {
+    pub fn word_str(&self) -> String {
+        word_str(&self.guess)
+    }
+
+    // word,guesses,num_candidates, the depths aren't kept
+    pub fn to_csv(&self) -> String {
+        format!(
+            "{},{},{}",
+            self.word_str(),
+            self.guesses,
+            self.num_candidates
+        )
+    }
+
+    pub fn from_csv(line: &str) -> Option<GuessResult> {
+        let mut fields = line.trim().split(',');
+        let word = fields.next()?;
+        if word.chars().count() != WORD_LENGTH {
+            return None;
+        }
+        Some(GuessResult {
+            guess: to_array(word),
+            guesses: fields.next()?.parse().ok()?,
+            num_candidates: fields.next()?.parse().ok()?,
+            depths: Vec::new(),
+            partition_sizes: Vec::new(),
+        })
+    }
+
+    // e.g. "min 1 / median 3 / max 76"
+    pub fn partition_summary(&self) -> String {
+        let sizes = &self.partition_sizes;
+        match (sizes.first(), sizes.last()) {
+            (Some(min), Some(max)) => format!(
+                "min {} / median {} / max {}",
+                min,
+                sizes[sizes.len() / 2],
+                max
+            ),
+            _ => String::from("none"),
+        }
+    }
+
+    pub fn depth_of(&self, answer: &Word) -> usize {
+        self.depths
+            .iter()
+            .find(|(w, _)| w == answer)
+            .map(|(_, d)| *d)
+            .unwrap()
+    }
+
+    // how many candidates are solved in 1, 2, 3, ... guesses
+    pub fn distribution(&self) -> Vec<usize> {
+        let max = self.depths.iter().map(|(_, d)| *d).max().unwrap_or(0);
+        let mut dist = vec![0; max];
+        self.depths.iter().for_each(|(_, d)| dist[d - 1] += 1);
+        dist
+    }
+}
+
+fn partition_sizes(patterns: impl IntoIterator<Item = Pattern>) -> Vec<usize> {
+    let mut counts = [0; NUM_PATTERNS];
+    patterns.into_iter().for_each(|p| counts[p as usize] += 1);
+    let mut sizes: Vec<usize> = counts.iter().copied().filter(|&n| n > 0).collect();
+    sizes.sort_unstable();
+    sizes
+}
+
+// depth of an answer once the guess has been played and the follow-up is known
+fn depth_after(guess: &Word, answer: &Word, next: &GuessResult) -> usize {
+    if guess == answer {
+        1
+    } else {
+        1 + next.depth_of(answer)
+    }
+}
+
+pub fn format_distribution(dist: &[usize]) -> String {
+    dist.iter()
+        .enumerate()
+        .map(|(i, n)| format!("{}: {}", i + 1, n))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl fmt::Display for GuessResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Word: {:?} Guesses: {} Num: {}",
+            self.word_str(),
+            self.guesses,
+            self.num_candidates
+        )
+    }
+}
+
+// how repeated letters in a guess are colored, for feedback entered from another
+// game; the solver's own patterns always follow Standard
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum DuplicateMode {
+    // NYT rules: a letter is only marked as many times as the answer has it, greens
+    // first, then yellows left to right, any extra copies gray
+    #[default]
+    Standard,
+    // every copy of a letter the answer contains is marked
+    Lenient,
+}
+
+impl FromStr for DuplicateMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<DuplicateMode, String> {
+        match s {
+            "standard" => Ok(DuplicateMode::Standard),
+            "lenient" => Ok(DuplicateMode::Lenient),
+            _ => Err(format!("unknown duplicate mode: {}", s)),
+        }
+    }
+}
+
+pub fn check(answer: &Word, guess: &Word, mode: DuplicateMode) -> Facts {
+    feedback_facts(guess, &check_array(answer, guess, mode))
+}
+
+// check's feedback without the allocation, the letter and position implied by the index
+pub fn check_array(answer: &Word, guess: &Word, mode: DuplicateMode) -> [Feedback; WORD_LENGTH] {
+    if guess == answer {
+        return [Feedback::Correct; WORD_LENGTH];
+    }
+    // answer letters not already matched by a green, used up by yellows in Standard
+    let mut spare = ['\0'; WORD_LENGTH];
+    for i in 0..WORD_LENGTH {
+        if guess[i] != answer[i] {
+            spare[i] = answer[i];
+        }
+    }
+
+    let mut res = [Feedback::NotUsed; WORD_LENGTH];
+    for i in 0..WORD_LENGTH {
+        if guess[i] == answer[i] {
+            res[i] = Feedback::Correct;
+        } else if mode == DuplicateMode::Lenient && answer.contains(&guess[i]) {
+            res[i] = Feedback::Used;
+        } else if let Some(j) = spare.iter().position(|c| *c == guess[i]) {
+            if mode == DuplicateMode::Standard {
+                spare[j] = '\0';
+            }
+            res[i] = Feedback::Used;
+        }
+    }
+    res
+}
+
+pub fn feedback_facts(guess: &Word, feedback: &[Feedback; WORD_LENGTH]) -> Facts {
+    (0..WORD_LENGTH)
+        .map(|i| build_fact(feedback[i], guess[i], i))
+        .collect()
+}
+
+// a turn's feedback with every tile green
+pub fn is_solved(facts: &Facts) -> bool {
+    facts.len() == WORD_LENGTH && facts.iter().all(|f| f.feedback == Feedback::Correct)
+}
+
+pub fn word_str(w: &Word) -> String {
+    w.iter().collect()
+}
+
+pub fn to_array(s: &str) -> Word {
+    s.chars().collect::<Vec<_>>().as_slice().try_into().unwrap()
+}
+
+// panics unless both are WORD_LENGTH letters, for trusted words like the list's
+pub fn check_str(answer: &str, guess: &str) -> Facts {
+    check(&to_array(answer), &to_array(guess), DuplicateMode::Standard)
+}
+
+// None instead of a panic when either isn't WORD_LENGTH letters, for unchecked input in loops
+// where parse_word's error message isn't wanted
+pub fn try_check(answer: &[char], guess: &[char]) -> Option<Facts> {
+    Some(check(
+        &answer.try_into().ok()?,
+        &guess.try_into().ok()?,
+        DuplicateMode::Standard,
+    ))
+}
+
+// facts for a guess from its tile colors: G(reen), Y(ellow), B(lack) or ? if unsure
+// surrounding whitespace from pasted input is ignored and letters are lowercased
+pub fn parse_feedback(guess: &str, colors: &str) -> Result<Facts, String> {
+    let letters: Vec<char> = guess.trim().to_lowercase().chars().collect();
+    let tiles: Vec<char> = colors.trim().chars().collect();
+    if letters.len() != WORD_LENGTH || tiles.len() != WORD_LENGTH {
+        return Err(format!(
+            "feedback needs {} letters and {} colors, got {:?} {:?}",
+            WORD_LENGTH, WORD_LENGTH, guess, colors
+        ));
+    }
+    letters
+        .iter()
+        .zip(&tiles)
+        .enumerate()
+        .map(|(i, (&l, &t))| {
+            let f = match t.to_ascii_uppercase() {
+                _ if l == SEPARATOR => Feedback::Correct,
+                'G' => Feedback::Correct,
+                'Y' => Feedback::Used,
+                'B' => Feedback::NotUsed,
+                '?' => Feedback::Unknown,
+                _ => return Err(format!("unknown color {:?} in {:?}", t, colors)),
+            };
+            Ok(build_fact(f, l, i))
+        })
+        .collect()
+}
+
+// one word per line, tolerating CRLF endings, stray whitespace and a UTF-8 BOM
+// blank lines are skipped
+pub fn parse_words(data: &str) -> Words {
+    data.trim_start_matches('\u{feff}')
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(to_array)
+        .collect()
+}
+
+// every non blank line not len letters long, with its line number
+pub fn validate_lengths(data: &str, len: usize) -> Result<(), Vec<(usize, String)>> {
+    let bad: Vec<(usize, String)> = data
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .enumerate()
+        .map(|(i, l)| (i + 1, l.trim()))
+        .filter(|(_, l)| !l.is_empty() && l.chars().count() != len)
+        .map(|(i, l)| (i, l.to_string()))
+        .collect();
+    if bad.is_empty() {
+        Ok(())
+    } else {
+        Err(bad)
+    }
+}
+
+// the answer list built into the binary, used unless --words names others
+pub const DEFAULT_WORDS: &str = include_str!("../data/wordle-answers-alphabetical.txt");
+
+// text, or the binary format when the file starts with WORDS_BIN_MAGIC
+pub fn load_words(path: &str) -> io::Result<Words> {
+    let bytes = fs::read(path)?;
+    if bytes.starts_with(WORDS_BIN_MAGIC) {
+        return words_from_bin(&bytes);
+    }
+    let data =
+        String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    validate_lengths(&data, WORD_LENGTH).map_err(|bad| {
+        let lines: Vec<String> = bad
+            .iter()
+            .map(|(i, w)| format!("line {}: {:?}", i, w))
+            .collect();
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "words must be {} letters, {}",
+                WORD_LENGTH,
+                lines.join(", ")
+            ),
+        )
+    })?;
+    Ok(parse_words(&data))
+}
+
+// "WRDL", then the word length as one byte and the word count as a little endian u32,
+// then each word's letters as WORD_LENGTH ASCII bytes
+pub const WORDS_BIN_MAGIC: &[u8] = b"WRDL";
+pub const WORDS_BIN_HEADER: usize = 9;
+
+pub fn words_to_bin(words: &Words) -> io::Result<Vec<u8>> {
+    let mut out = Vec::with_capacity(WORDS_BIN_HEADER + words.len() * WORD_LENGTH);
+    out.extend_from_slice(WORDS_BIN_MAGIC);
+    out.push(WORD_LENGTH as u8);
+    out.extend_from_slice(&(words.len() as u32).to_le_bytes());
+    for w in words {
+        for c in w {
+            if !c.is_ascii() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{} isn't ASCII", word_str(w)),
+                ));
+            }
+            out.push(*c as u8);
+        }
+    }
+    Ok(out)
+}
+
+pub fn words_from_bin(bytes: &[u8]) -> io::Result<Words> {
+    let bad = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+    if bytes.len() < WORDS_BIN_HEADER || !bytes.starts_with(WORDS_BIN_MAGIC) {
+        return Err(bad("not a binary word list".to_string()));
+    }
+    let len = bytes[4] as usize;
+    if len != WORD_LENGTH {
+        return Err(bad(format!(
+            "words must be {} letters, the list has {}",
+            WORD_LENGTH, len
+        )));
+    }
+    let count = u32::from_le_bytes(bytes[5..WORDS_BIN_HEADER].try_into().unwrap()) as usize;
+    let body = &bytes[WORDS_BIN_HEADER..];
+    if body.len() != count * WORD_LENGTH {
+        return Err(bad(format!(
+            "expected {} words, found {} bytes of them",
+            count,
+            body.len()
+        )));
+    }
+    Ok(body
+        .chunks_exact(WORD_LENGTH)
+        .map(|w| std::array::from_fn(|i| w[i] as char))
+        .collect())
+}
+
+pub fn save_words_bin(words: &Words, path: &str) -> io::Result<()> {
+    fs::write(path, words_to_bin(words)?)
+}
+
+pub fn load_words_bin(path: &str) -> io::Result<Words> {
+    words_from_bin(&fs::read(path)?)
+}
+
+// the lists in order with later repeats of a word dropped
+pub fn merge_words(lists: &[Words]) -> Words {
+    let mut seen = HashSet::new();
+    lists
+        .iter()
+        .flatten()
+        .filter(|w| seen.insert(**w))
+        .copied()
+        .collect()
+}
+
+#[derive(Clone, Debug)]
+enum Atom {
+    Any,
+    Letter(char),
+    // negated, inclusive ranges
+    Class(bool, Vec<(char, char)>),
+}
+
+impl Atom {
+    fn accepts(&self, c: char) -> bool {
+        match self {
+            Atom::Any => true,
+            Atom::Letter(l) => *l == c,
+            Atom::Class(negated, ranges) => {
+                ranges.iter().any(|&(lo, hi)| lo <= c && c <= hi) != *negated
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Repeat {
+    Once,
+    ZeroOrOne,
+    ZeroOrMore,
+    OneOrMore,
+}
+
+// The subset of regex syntax worth having for word lists: letters, `.`, classes like
+// `[aeiou]`, `[^a-f]`, the `?`, `*` and `+` repeats and the `^` and `$` anchors.
+// Hand rolled so filtering a list doesn't drag in the regex crate.
+#[derive(Debug)]
+pub struct WordRegex {
+    pieces: Vec<(Atom, Repeat)>,
+    anchor_start: bool,
+    anchor_end: bool,
+}
+
+impl WordRegex {
+    pub fn new(pattern: &str) -> Result<WordRegex, String> {
+        let err = |what: &str| Err(format!("invalid pattern {:?}: {}", pattern, what));
+        let mut chars = pattern.chars().peekable();
+        let anchor_start = chars.next_if_eq(&'^').is_some();
+        let mut anchor_end = false;
+        let mut pieces = Vec::new();
+        while let Some(c) = chars.next() {
+            let atom = match c {
+                '$' if chars.peek().is_none() => {
+                    anchor_end = true;
+                    break;
+                }
+                '.' => Atom::Any,
+                '[' => {
+                    let negated = chars.next_if_eq(&'^').is_some();
+                    let mut ranges = Vec::new();
+                    loop {
+                        match chars.next() {
+                            None => return err("unterminated character class"),
+                            Some(']') => break,
+                            Some(lo) => {
+                                let hi = match chars.peek() {
+                                    Some('-') => {
+                                        chars.next();
+                                        match chars.next() {
+                                            Some(']') | None => return err("unfinished range"),
+                                            Some(hi) => hi,
+                                        }
+                                    }
+                                    _ => lo,
+                                };
+                                ranges.push((lo, hi));
+                            }
+                        }
+                    }
+                    if ranges.is_empty() {
+                        return err("empty character class");
+                    }
+                    Atom::Class(negated, ranges)
+                }
+                '*' | '+' | '?' => return err("nothing to repeat"),
+                '(' | ')' | '|' | '{' | '}' | '\\' | '^' | '$' | ']' => {
+                    return err(&format!("{:?} is not supported", c))
+                }
+                c => Atom::Letter(c),
+            };
+            let repeat = match chars.peek() {
+                Some('?') => Repeat::ZeroOrOne,
+                Some('*') => Repeat::ZeroOrMore,
+                Some('+') => Repeat::OneOrMore,
+                _ => Repeat::Once,
+            };
+            if !matches!(repeat, Repeat::Once) {
+                chars.next();
+            }
+            pieces.push((atom, repeat));
+        }
+        Ok(WordRegex {
+            pieces,
+            anchor_start,
+            anchor_end,
+        })
+    }
+
+    pub fn is_match(&self, s: &str) -> bool {
+        let chars: Vec<char> = s.chars().collect();
+        let last = if self.anchor_start { 0 } else { chars.len() };
+        (0..=last).any(|i| self.match_here(&self.pieces, &chars[i..]))
+    }
+
+    fn match_here(&self, pieces: &[(Atom, Repeat)], s: &[char]) -> bool {
+        match pieces.split_first() {
+            None => !self.anchor_end || s.is_empty(),
+            Some(((atom, repeat), rest)) => {
+                let (min, max) = match repeat {
+                    Repeat::Once => (1, 1),
+                    Repeat::ZeroOrOne => (0, 1),
+                    Repeat::ZeroOrMore => (0, usize::MAX),
+                    Repeat::OneOrMore => (1, usize::MAX),
+                };
+                let n = s.iter().take_while(|&&c| atom.accepts(c)).take(max).count();
+                (min..=n).rev().any(|k| self.match_here(rest, &s[k..]))
+            }
+        }
+    }
+}
+
+// feedback of a guess encoded in base 3, position 0 being the lowest digit
+pub fn pattern(answer: &Word, guess: &Word) -> Pattern {
+    feedback_pattern(&check_array(answer, guess, DuplicateMode::Standard))
+}
+
+// panics on Unknown, which no pattern stands for
+pub fn feedback_pattern(feedback: &[Feedback; WORD_LENGTH]) -> Pattern {
+    feedback.iter().rev().fold(0, |p, f| {
+        p * 3
+            + match f {
+                Feedback::NotUsed => 0,
+                Feedback::Used => 1,
+                Feedback::Correct => 2,
+                Feedback::Unknown => unreachable!("patterns have no unknown color"),
+            }
+    })
+}
+
+// tile colors of a pattern, G(reen) Y(ellow) B(lack) from position 0
+pub fn pattern_string(p: Pattern) -> String {
+    let mut p = p;
+    (0..WORD_LENGTH)
+        .map(|_| {
+            let c = ['B', 'Y', 'G'][(p % 3) as usize];
+            p /= 3;
+            c
+        })
+        .collect()
+}
+
+// Patterns for every (guess, answer) pair of a word list.
+// Materializing the matrix costs n * n bytes (~5MB for the 2315 answers, ~170MB for
+// 13k words) but makes every lookup free. Streaming keeps no table and recomputes
+// each pattern with `check`, trading time for memory; since lookups only happen
+// against the current candidates, the work shrinks every turn.
+pub struct PatternTable<'a> {
+    words: &'a Words,
+    matrix: Option<Vec<Pattern>>,
+}
+
+impl<'a> PatternTable<'a> {
+    pub fn new(words: &'a Words, streaming: bool) -> PatternTable<'a> {
+        let matrix = if streaming {
+            None
+        } else {
+            Some(
+                words
+                    .par_iter()
+                    .flat_map_iter(|g| words.iter().map(move |a| pattern(a, g)))
+                    .collect(),
+            )
+        };
+        PatternTable { words, matrix }
+    }
+
+    pub fn get(&self, guess: usize, answer: usize) -> Pattern {
+        match &self.matrix {
+            Some(m) => m[guess * self.words.len() + answer],
+            None => pattern(&self.words[answer], &self.words[guess]),
+        }
+    }
+}
+
+// a full row of feedback for one guess, as check and parse_feedback produce
+fn feedback_row(facts: &[Fact]) -> Option<&[Fact]> {
+    let row = facts.get(..WORD_LENGTH)?;
+    if row.iter().enumerate().all(|(i, f)| f.position == Some(i)) {
+        Some(row)
+    } else {
+        None
+    }
+}
+
+pub fn letter_counts(w: &Word) -> [u8; NUM_CHARS] {
+    let mut counts = [0; NUM_CHARS];
+    w.iter()
+        .filter_map(|c| letter_index(*c))
+        .for_each(|i| counts[i] += 1);
+    counts
+}
+
+// letter counts of every word in a list, computed once and reused by each filter
+pub fn count_letters(words: &Words) -> Vec<[u8; NUM_CHARS]> {
+    words.iter().map(letter_counts).collect()
+}
+
+// Standard coloring of a full row against a word: a letter shown green or yellow n
+// times needs at least n copies, exactly n once another copy of it was gray, and a
+// yellow can't follow a gray of the same letter since yellows are handed out first
+fn row_matches(w: &Word, counts: &[u8; NUM_CHARS], row: &[Fact]) -> bool {
+    for (i, f) in row.iter().enumerate() {
+        let placed = match f.feedback {
+            Feedback::Correct => w[i] == f.letter,
+            Feedback::Used | Feedback::NotUsed => w[i] != f.letter,
+            Feedback::Unknown => true,
+        };
+        if !placed {
+            return false;
+        }
+        if row[..i].iter().any(|g| g.letter == f.letter) {
+            continue;
+        }
+
+        let (mut shown, mut unknown, mut gray) = (0, 0, false);
+        for g in row[i..].iter().filter(|g| g.letter == f.letter) {
+            match g.feedback {
+                Feedback::Correct => shown += 1,
+                Feedback::Used if gray => return false,
+                Feedback::Used => shown += 1,
+                Feedback::NotUsed => gray = true,
+                Feedback::Unknown => unknown += 1,
+            }
+        }
+        if let Some(l) = letter_index(f.letter) {
+            if counts[l] < shown || (gray && counts[l] > shown + unknown) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+// In Standard mode the colors of a repeated letter depend on each other, so a full row
+// is matched as a whole; facts entered on their own only say where a letter is, isn't,
+// or that it's absent once no other fact places it.
+pub fn matches(w: &Word, facts: &Facts, mode: DuplicateMode) -> bool {
+    matches_counted(w, &letter_counts(w), facts, mode)
+}
+
+pub fn matches_counted(
+    w: &Word,
+    counts: &[u8; NUM_CHARS],
+    facts: &Facts,
+    mode: DuplicateMode,
+) -> bool {
+    if mode == DuplicateMode::Lenient {
+        return !facts.iter().any(|f| match &f.feedback {
+            Feedback::Correct => f.position.is_some_and(|p| w[p] != f.letter),
+            Feedback::Used => {
+                f.position.is_some_and(|p| w[p] == f.letter) || !w.contains(&f.letter)
+            }
+            Feedback::NotUsed => w.contains(&f.letter),
+            Feedback::Unknown => false,
+        });
+    }
+
+    let mut rest = &facts[..];
+    while !rest.is_empty() {
+        if let Some(row) = feedback_row(rest) {
+            if !row_matches(w, counts, row) {
+                return false;
+            }
+            rest = &rest[WORD_LENGTH..];
+        } else {
+            let f = &rest[0];
+            let at = f.position.is_some_and(|p| w[p] == f.letter);
+            let ok = match f.feedback {
+                Feedback::Correct => f.position.is_none() || at,
+                Feedback::Used => !at && w.contains(&f.letter),
+                Feedback::NotUsed => {
+                    let placed = facts.iter().any(|g| {
+                        g.letter == f.letter
+                            && matches!(g.feedback, Feedback::Correct | Feedback::Used)
+                    });
+                    !at && (placed || !w.contains(&f.letter))
+                }
+                Feedback::Unknown => true,
+            };
+            if !ok {
+                return false;
+            }
+            rest = &rest[1..];
+        }
+    }
+    true
+}
+
+// what the facts say with Used split into its two halves: the letter is in the word
+// (a minimum count) and it isn't at this position (forbidden)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Constraints {
+    pub required: [Option<char>; WORD_LENGTH],
+    pub forbidden: [Vec<char>; WORD_LENGTH],
+    pub min_counts: [u8; NUM_CHARS],
+    pub max_counts: [Option<u8>; NUM_CHARS],
+}
+
+impl Constraints {
+    pub fn matches(&self, w: &Word) -> bool {
+        let counts = letter_counts(w);
+        (0..WORD_LENGTH).all(|i| {
+            self.required[i].is_none_or(|l| w[i] == l) && !self.forbidden[i].contains(&w[i])
+        }) && (0..NUM_CHARS).all(|l| {
+            counts[l] >= self.min_counts[l] && self.max_counts[l].is_none_or(|m| counts[l] <= m)
+        })
+    }
+}
+
+// Standard mode's reading of the facts, full rows counting a letter's colors together
+pub fn constraints_from_facts(facts: &Facts) -> Constraints {
+    let mut c = Constraints {
+        required: [None; WORD_LENGTH],
+        forbidden: Default::default(),
+        min_counts: [0; NUM_CHARS],
+        max_counts: [None; NUM_CHARS],
+    };
+    let cap = |c: &mut Constraints, l: usize, max: u8| {
+        c.max_counts[l] = Some(c.max_counts[l].map_or(max, |m| m.min(max)));
+    };
+    let mut rest = &facts[..];
+    while !rest.is_empty() {
+        let row = feedback_row(rest).unwrap_or(&rest[..1]);
+        for f in row {
+            if let Some(p) = f.position {
+                match f.feedback {
+                    Feedback::Correct => c.required[p] = Some(f.letter),
+                    Feedback::Used | Feedback::NotUsed => c.forbidden[p].push(f.letter),
+                    Feedback::Unknown => {}
+                }
+            }
+        }
+        for l in ASCII_LOWER
+            .iter()
+            .filter(|l| row.iter().any(|f| f.letter == **l))
+        {
+            let i = letter_index(*l).unwrap();
+            let of = |fb: Feedback| {
+                row.iter()
+                    .filter(|f| f.letter == *l && f.feedback == fb)
+                    .count() as u8
+            };
+            let shown = of(Feedback::Correct) + of(Feedback::Used);
+            c.min_counts[i] = c.min_counts[i].max(shown);
+            if of(Feedback::NotUsed) > 0 {
+                let placed_elsewhere = row.len() == 1
+                    && facts.iter().any(|g| {
+                        g.letter == *l && matches!(g.feedback, Feedback::Correct | Feedback::Used)
+                    });
+                if !placed_elsewhere {
+                    cap(&mut c, i, shown + of(Feedback::Unknown));
+                }
+            }
+        }
+        rest = &rest[row.len()..];
+    }
+    c
+}
+
+// what the facts establish in plain English, positions counted from 1, e.g.
+// "Greens: R at 2. Present (not here): A (not 1). Absent: C, L, P, S."
+pub fn summarize_facts(facts: &Facts) -> String {
+    let c = constraints_from_facts(facts);
+    let upper = |l: &char| l.to_ascii_uppercase();
+    let mut parts = Vec::new();
+
+    let greens: Vec<String> = (0..WORD_LENGTH)
+        .filter_map(|i| c.required[i].map(|l| format!("{} at {}", upper(&l), i + 1)))
+        .collect();
+    if !greens.is_empty() {
+        parts.push(format!("Greens: {}.", greens.join(", ")));
+    }
+    let mut present = Vec::new();
+    let mut absent = Vec::new();
+    let mut exact = Vec::new();
+    for (i, l) in ASCII_LOWER.iter().enumerate() {
+        let placed = c.required.iter().filter(|r| **r == Some(*l)).count() as u8;
+        if c.min_counts[i] > placed {
+            let not: Vec<String> = (0..WORD_LENGTH)
+                .filter(|p| c.forbidden[*p].contains(l))
+                .map(|p| (p + 1).to_string())
+                .collect();
+            present.push(match not.is_empty() {
+                true => upper(l).to_string(),
+                false => format!("{} (not {})", upper(l), not.join(", ")),
+            });
+        }
+        match c.max_counts[i] {
+            Some(0) => absent.push(upper(l).to_string()),
+            Some(n) if n == c.min_counts[i] => exact.push(format!("{} {}", n, upper(l))),
+            _ => {}
+        }
+    }
+    if !present.is_empty() {
+        parts.push(format!("Present (not here): {}.", present.join(", ")));
+    }
+    if !exact.is_empty() {
+        parts.push(format!("Exactly: {}.", exact.join(", ")));
+    }
+    if !absent.is_empty() {
+        parts.push(format!("Absent: {}.", absent.join(", ")));
+    }
+    if parts.is_empty() {
+        return "Nothing known yet.".to_string();
+    }
+    parts.join(" ")
+}
+
+// facts no word could satisfy, caught before filtering so the error can say why
+pub fn validate_facts(facts: &Facts) -> Result<(), String> {
+    let mut greens: [Option<char>; WORD_LENGTH] = [None; WORD_LENGTH];
+    for f in facts.iter().filter(|f| f.feedback == Feedback::Correct) {
+        let Some(pos) = f.position else { continue };
+        match greens[pos] {
+            Some(l) if l != f.letter => {
+                return Err(format!(
+                    "position {} can't be both {:?} and {:?}",
+                    pos + 1,
+                    l,
+                    f.letter
+                ))
+            }
+            _ => greens[pos] = Some(f.letter),
+        }
+    }
+    Ok(())
+}
+
+// filter_words with the letter counts of `words` already at hand
+pub fn filter_words_counted(
+    words: &Words,
+    counts: &[[u8; NUM_CHARS]],
+    facts: &Facts,
+    mode: DuplicateMode,
+) -> Words {
+    words
+        .iter()
+        .zip(counts)
+        .filter(|(w, c)| matches_counted(w, c, facts, mode))
+        .map(|(w, _)| *w)
+        .collect()
+}
+
+pub fn filter_words(words: &Words, facts: &Facts, mode: DuplicateMode) -> Words {
+    let mut filtered: Words = Vec::new();
+    words
+        .iter()
+        .filter(|w| matches(w, facts, mode))
+        .for_each(|w| filtered.push(*w));
+    filtered
+}
+
+// indices into `words` of the words consistent with the facts
+pub fn candidate_indices(words: &Words, facts: &Facts, mode: DuplicateMode) -> Vec<usize> {
+    (0..words.len())
+        .filter(|&i| matches(&words[i], facts, mode))
+        .collect()
+}
+
+// the facts as filtering reads them: full rows kept whole, facts on their own one
+// apiece, sorted and without repeats, so turns entered in any order come out the same
+pub fn normalize_facts(facts: &Facts) -> Vec<Vec<Fact>> {
+    let mut units = Vec::new();
+    let mut rest = &facts[..];
+    while !rest.is_empty() {
+        let unit = feedback_row(rest).unwrap_or(&rest[..1]);
+        units.push(unit.to_vec());
+        rest = &rest[unit.len()..];
+    }
+    units.sort();
+    units.dedup();
+    units
+}
+
+pub const FILTER_CACHE_SIZE: usize = 64;
+
+// candidate indices of recently filtered fact sets by normalize_facts, the least recently
+// used dropped once FILTER_CACHE_SIZE are kept
+#[derive(Clone, Debug, Default)]
+pub struct FilterCache {
+    entries: HashMap<Vec<Vec<Fact>>, Vec<usize>>,
+    // keys, least recently used first
+    order: VecDeque<Vec<Vec<Fact>>>,
+    hits: usize,
+}
+
+impl FilterCache {
+    fn get(&mut self, facts: &Facts) -> Option<Vec<usize>> {
+        let key = normalize_facts(facts);
+        let found = self.entries.get(&key)?.clone();
+        self.order.retain(|k| *k != key);
+        self.order.push_back(key);
+        self.hits += 1;
+        Some(found)
+    }
+
+    fn insert(&mut self, facts: &Facts, candidates: Vec<usize>) {
+        let key = normalize_facts(facts);
+        if self.entries.insert(key.clone(), candidates).is_some() {
+            self.order.retain(|k| *k != key);
+        } else if self.order.len() == FILTER_CACHE_SIZE {
+            if let Some(old) = self.order.pop_front() {
+                self.entries.remove(&old);
+            }
+        }
+        self.order.push_back(key);
+    }
+}
+
+// number of candidates falling into each pattern for the given guess
+pub fn partition_counts(table: &PatternTable, guess: usize, candidates: &[usize]) -> Vec<usize> {
+    let mut counts = vec![0; NUM_PATTERNS];
+    candidates
+        .iter()
+        .for_each(|&a| counts[table.get(guess, a) as usize] += 1);
+    counts
+}
+
+// log2 of the candidates left, zero once at most one is
+pub fn remaining_entropy(n: usize) -> f64 {
+    (n.max(1) as f64).log2()
+}
+
+pub fn entropy(counts: &[usize]) -> f64 {
+    let total: usize = counts.iter().sum();
+    counts
+        .iter()
+        .filter(|&&n| n > 0)
+        .map(|&n| {
+            let p = n as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+// how a single guess is scored against the candidates
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Strategy {
+    // maximize the information gained
+    #[default]
+    Entropy,
+    // minimize the expected number of candidates left
+    Expected,
+    // minimize the largest group of candidates left
+    Minimax,
+}
+
+impl FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Strategy, String> {
+        match s {
+            "entropy" => Ok(Strategy::Entropy),
+            "expected" => Ok(Strategy::Expected),
+            "worst" | "minimax" => Ok(Strategy::Minimax),
+            _ => Err(format!("unknown metric: {}", s)),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GuessEval {
+    pub groups: usize,
+    pub worst: usize,
+    pub expected: f64,
+    pub entropy: f64,
+}
+
+impl GuessEval {
+    pub fn from_counts(counts: &[usize]) -> GuessEval {
+        let total: usize = counts.iter().sum();
+        GuessEval {
+            groups: counts.iter().filter(|&&n| n > 0).count(),
+            worst: *counts.iter().max().unwrap_or(&0),
+            expected: counts.iter().map(|&n| (n * n) as f64).sum::<f64>() / total.max(1) as f64,
+            entropy: entropy(counts),
+        }
+    }
+
+    // the value the strategy looks at, higher entropy is better and lower otherwise
+    pub fn score(&self, strategy: Strategy) -> f64 {
+        match strategy {
+            Strategy::Entropy => self.entropy,
+            Strategy::Expected => self.expected,
+            Strategy::Minimax => self.worst as f64,
+        }
+    }
+
+    // lower is better
+    pub fn cost(&self, strategy: Strategy) -> f64 {
+        match strategy {
+            Strategy::Entropy => -self.entropy,
+            _ => self.score(strategy),
+        }
+    }
+
+    // alpha of the entropy cost and the rest of the worst case's remaining bits, both
+    // in bits so neither swamps the other; lower is better
+    pub fn blended_cost(&self, alpha: f64) -> f64 {
+        -alpha * self.entropy + (1.0 - alpha) * remaining_entropy(self.worst)
+    }
+}
+
+impl fmt::Display for GuessEval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Entropy: {:.p$} Expected: {:.p$} Worst: {} Groups: {}",
+            self.entropy,
+            self.expected,
+            self.worst,
+            self.groups,
+            p = f.precision().unwrap_or(DEFAULT_PRECISION)
+        )
+    }
+}
+
+// scores any guess, in the list or not, against the candidates
+pub fn evaluate_guess(candidates: &Words, guess: &Word) -> GuessEval {
+    let mut counts = vec![0; NUM_PATTERNS];
+    candidates
+        .iter()
+        .for_each(|a| counts[pattern(a, guess) as usize] += 1);
+    GuessEval::from_counts(&counts)
+}
+
+pub fn sort_by_cost(ranked: &mut Vec<(Word, GuessEval)>, strategy: Strategy) {
+    ranked.sort_by(|a, b| a.1.cost(strategy).partial_cmp(&b.1.cost(strategy)).unwrap());
+}
+
+// ranks every word as a guess by how well it partitions the candidates
+pub fn rank_guesses(
+    table: &PatternTable,
+    guesses: &[usize],
+    candidates: &[usize],
+    strategy: Strategy,
+) -> Vec<(Word, GuessEval)> {
+    let mut ranked: Vec<(Word, GuessEval)> = guesses
+        .par_iter()
+        .map(|&g| {
+            let counts = partition_counts(table, g, candidates);
+            (table.words[g], GuessEval::from_counts(&counts))
+        })
+        .collect();
+    sort_by_cost(&mut ranked, strategy);
+    ranked
+}
+
+// ranks a user supplied list of guesses against the candidates
+pub fn rank_openers(
+    candidates: &Words,
+    openers: &Words,
+    strategy: Strategy,
+) -> Vec<(Word, GuessEval)> {
+    let mut ranked: Vec<(Word, GuessEval)> = openers
+        .par_iter()
+        .map(|g| (*g, evaluate_guess(candidates, g)))
+        .collect();
+    sort_by_cost(&mut ranked, strategy);
+    ranked
+}
+
+// the grouping a guess induces on the candidates, independent of the pattern labels,
+// so guesses splitting the candidates the same way (e.g. anagrams) compare equal
+pub fn partition_signature(candidates: &Words, guess: &Word) -> Vec<u8> {
+    let mut labels = [u8::MAX; NUM_PATTERNS];
+    let mut next = 0;
+    candidates
+        .iter()
+        .map(|a| {
+            let p = pattern(a, guess) as usize;
+            if labels[p] == u8::MAX {
+                labels[p] = next;
+                next += 1;
+            }
+            labels[p]
+        })
+        .collect()
+}
+
+// keeps the first n distinct partitions of a ranking, represented by their
+// alphabetically first guess, or the first in the ranking with prefer_order
+pub fn collapse_equivalent(
+    ranked: &[(Word, GuessEval)],
+    candidates: &Words,
+    n: usize,
+    strategy: Strategy,
+    prefer_order: bool,
+) -> Vec<(Word, GuessEval)> {
+    let mut kept: Vec<(Word, GuessEval)> = Vec::new();
+    let mut seen: HashMap<Vec<u8>, usize> = HashMap::new();
+    for (w, eval) in ranked {
+        if kept.len() >= n && eval.cost(strategy) > kept[kept.len() - 1].1.cost(strategy) {
+            break;
+        }
+        match seen.entry(partition_signature(candidates, w)) {
+            Entry::Occupied(e) => {
+                let i = *e.get();
+                if !prefer_order && *w < kept[i].0 {
+                    kept[i].0 = *w;
+                }
+            }
+            Entry::Vacant(e) => {
+                if kept.len() < n {
+                    e.insert(kept.len());
+                    kept.push((*w, eval.clone()));
+                }
+            }
+        }
+    }
+    kept
+}
+
+// the guess showing the most distinct patterns over the candidates, a candidate when
+// one ties; panics without any candidates or words
+pub fn minimal_distinguisher(words: &Words, candidates: &Words) -> Word {
+    *candidates
+        .iter()
+        .chain(words.iter())
+        .min_by_key(|g| Reverse(evaluate_guess(candidates, g).groups))
+        .expect("no guesses to choose from")
+}
+
+// distinct (position, letter) pairs among the words, the choices a next guess has
+fn letter_spread(words: &Words) -> usize {
+    let mut seen = [[false; NUM_CHARS]; WORD_LENGTH];
+    for w in words {
+        for (i, c) in w.iter().enumerate() {
+            if let Some(l) = letter_index(*c) {
+                seen[i][l] = true;
+            }
+        }
+    }
+    seen.iter().flatten().filter(|s| **s).count()
+}
+
+// the guess leaving the candidates most spread across letters: each pattern's group
+// scores its letter_spread, the solved group none, and the guess scores the sum over
+// its groups. Highest sum wins, then a candidate, then the list order of words. The
+// result's depths follow Entropy play after the guess; panics without any candidates
+pub fn flexibility_guess(words: &Words, candidates: &Words) -> GuessResult {
+    let solved = (NUM_PATTERNS - 1) as Pattern;
+    let score = |g: &Word| -> usize {
+        partition_by_pattern(candidates, g)
+            .iter()
+            .filter(|(p, _)| *p != solved)
+            .map(|(_, group)| letter_spread(group))
+            .sum()
+    };
+    let scores: Vec<usize> = words.par_iter().map(score).collect();
+    let guess = words
+        .iter()
+        .zip(&scores)
+        .max_by_key(|(g, s)| (**s, candidates.contains(g), Reverse(*g)))
+        .map(|(g, _)| *g)
+        .expect("no guesses to choose from");
+    solve_all(words, candidates, &guess, Strategy::Entropy)
+}
+
+// the weight adaptive_guess gives entropy with `remaining` guesses left: all of it with
+// a full game ahead, none on the last guess where only the worst case matters
+pub fn adaptive_alpha(remaining: usize) -> f64 {
+    (remaining.saturating_sub(1) as f64 / (MAX_TURNS - 1) as f64).min(1.0)
+}
+
+// the guess with the lowest blended cost for the guesses left, a candidate on ties and
+// only candidates on the last guess since anything else can't win. The result's depths
+// follow Entropy play after the guess; panics without any candidates
+pub fn adaptive_guess(words: &Words, candidates: &Words, remaining: usize) -> GuessResult {
+    let alpha = adaptive_alpha(remaining);
+    let pool = if remaining <= 1 { candidates } else { words };
+    let guess = if candidates.len() <= 2 {
+        candidates[0]
+    } else {
+        pool.par_iter()
+            .map(|g| (*g, evaluate_guess(candidates, g).blended_cost(alpha)))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .min_by(|(a, x), (b, y)| {
+                x.partial_cmp(y)
+                    .unwrap()
+                    .then(candidates.contains(b).cmp(&candidates.contains(a)))
+            })
+            .map(|(g, _)| g)
+            .expect("no guesses to choose from")
+    };
+    solve_all(words, candidates, &guess, Strategy::Entropy)
+}
+
+// pairs of words that, played as the first two guesses whatever the first shows, leave
+// at most max_worst candidates under any pair of patterns. Each pair once, in list order
+pub fn good_starter_pairs(words: &Words, max_worst: usize) -> Vec<(Word, Word)> {
+    let table = PatternTable::new(words, false);
+    let n = words.len();
+    let firsts: Vec<usize> = (0..n).collect();
+    firsts
+        .par_iter()
+        .flat_map_iter(|&a| {
+            let table = &table;
+            // candidates under each (first, second) pattern, cleared after every pair
+            let mut counts = vec![0; NUM_PATTERNS * NUM_PATTERNS];
+            (a + 1..n).filter_map(move |b| {
+                let key =
+                    |w: usize| table.get(a, w) as usize * NUM_PATTERNS + table.get(b, w) as usize;
+                let mut ok = true;
+                let mut seen = 0;
+                for w in 0..n {
+                    seen = w + 1;
+                    let k = key(w);
+                    counts[k] += 1;
+                    if counts[k] > max_worst {
+                        ok = false;
+                        break;
+                    }
+                }
+                (0..seen).for_each(|w| counts[key(w)] = 0);
+                ok.then(|| (table.words[a], table.words[b]))
+            })
+        })
+        .collect()
+}
+
+// the most informative guess that could also win this turn, only candidates considered
+pub fn exploit_guess(candidates: &Words) -> Option<Word> {
+    rank_openers(candidates, candidates, Strategy::Entropy)
+        .first()
+        .map(|(w, _)| *w)
+}
+
+// the most informative guess of all, whether or not it could be the answer
+pub fn explore_guess(words: &Words, candidates: &Words) -> Option<Word> {
+    rank_openers(candidates, words, Strategy::Entropy)
+        .first()
+        .map(|(w, _)| *w)
+        .filter(|_| !candidates.is_empty())
+}
+
+// exploit_guess played out with Entropy play after it; panics without any candidates
+pub fn best_exploit(words: &Words, candidates: &Words) -> GuessResult {
+    let guess = exploit_guess(candidates).expect("no candidates to choose from");
+    solve_all(words, candidates, &guess, Strategy::Entropy)
+}
+
+// explore_guess played out with Entropy play after it; panics without any candidates
+pub fn best_explore(words: &Words, candidates: &Words) -> GuessResult {
+    let guess = explore_guess(words, candidates).expect("no candidates to choose from");
+    solve_all(words, candidates, &guess, Strategy::Entropy)
+}
+
+// a guess splitting the candidates into singletons, so the next guess always wins;
+// candidates are tried first since they may also win right away
+pub fn find_guaranteed_two(words: &Words, candidates: &Words) -> Option<Word> {
+    if candidates.is_empty() {
+        return None;
+    }
+    candidates
+        .iter()
+        .chain(words.iter())
+        .find(|g| evaluate_guess(candidates, g).worst <= 1)
+        .copied()
+}
+
+// plain English summary of what a guess does to the candidates
+pub fn explain_guess(candidates: &Words, guess: &Word, precision: usize) -> String {
+    let eval = evaluate_guess(candidates, guess);
+    format!(
+        "{} narrows {} candidates into {} groups, worst case {} remain, expected {:.p$}",
+        word_str(guess),
+        candidates.len(),
+        eval.groups,
+        eval.worst,
+        eval.expected,
+        p = precision
+    )
+}
+
+// the guess to play next: the best scoring word, preferring candidates on ties since
+// they can win outright, and a candidate directly once two or fewer remain
+pub fn select_guess(words: &Words, candidates: &Words, strategy: Strategy) -> Option<Word> {
+    choose_guess(words, candidates, strategy).map(|c| c.guess())
+}
+
+// select_guess's pick, telling apart the candidates no guess can split
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Choice {
+    Guess(Word),
+    // every guess either splits off only itself or nothing at all, so the best left is
+    // trying the candidates one at a time; the guess is still the strategy's pick
+    DeadEnd(Word),
+}
+
+impl Choice {
+    pub fn guess(&self) -> Word {
+        match self {
+            Choice::Guess(g) | Choice::DeadEnd(g) => *g,
+        }
+    }
+}
+
+pub fn choose_guess(words: &Words, candidates: &Words, strategy: Strategy) -> Option<Choice> {
+    if candidates.len() <= 2 {
+        return candidates.first().copied().map(Choice::Guess);
+    }
+    let ranked = rank_openers(candidates, words, strategy);
+    let guess = choose_ranked(&ranked, candidates, strategy)?;
+    let n = candidates.len();
+    if ranked.iter().all(|(_, eval)| eval.worst + 1 >= n) {
+        Some(Choice::DeadEnd(guess))
+    } else {
+        Some(Choice::Guess(guess))
+    }
+}
+
+// how close two float scores must be to count as a tie
+pub const SCORE_EPSILON: f64 = 1e-9;
+
+// every word scoring as well as the best opener over the whole list, in ranking order,
+// where select_guess would settle on just one of them
+pub fn all_optimal_openers(words: &Words, strategy: Strategy) -> Vec<Word> {
+    let ranked = rank_openers(words, words, strategy);
+    let Some(best) = ranked.first().map(|(_, eval)| eval.cost(strategy)) else {
+        return Vec::new();
+    };
+    ranked
+        .iter()
+        .take_while(|(_, eval)| eval.cost(strategy) - best <= SCORE_EPSILON)
+        .map(|(w, _)| *w)
+        .collect()
+}
+
+// select_guess's pick from a ranking it already has
+fn choose_ranked(
+    ranked: &[(Word, GuessEval)],
+    candidates: &Words,
+    strategy: Strategy,
+) -> Option<Word> {
+    let best = ranked.first()?.1.cost(strategy);
+    ranked
+        .iter()
+        .take_while(|(_, eval)| eval.cost(strategy) == best)
+        .find(|(w, _)| candidates.contains(w))
+        .or_else(|| ranked.first())
+        .map(|(w, _)| *w)
+}
+
+// select_guess's pick, the best scoring other word and how much more that costs under
+// the strategy. A small gap means a slightly different list could flip the choice
+pub fn select_with_runner_up(
+    words: &Words,
+    candidates: &Words,
+    strategy: Strategy,
+) -> Option<(Word, Word, f64)> {
+    let ranked = rank_openers(candidates, words, strategy);
+    let best = match candidates.len() {
+        0 => return None,
+        1 | 2 => candidates[0],
+        _ => choose_ranked(&ranked, candidates, strategy)?,
+    };
+    let cost = |w: &Word| evaluate_guess(candidates, w).cost(strategy);
+    let (second, eval) = ranked.iter().find(|(w, _)| *w != best)?;
+    Some((best, *second, eval.cost(strategy) - cost(&best)))
+}
+
+// the strategy's play as a tree: a guess per node, one child per pattern it can show
+// that leaves several candidates
+#[derive(Clone, Debug)]
+pub struct DecisionNode {
+    // None once the depth cap is reached with several candidates left
+    pub guess: Option<Word>,
+    pub remaining: usize,
+    pub children: Vec<(Pattern, DecisionNode)>,
+    // patterns leaving a single candidate, which is guessed next
+    pub leaves: Vec<(Pattern, Word)>,
+}
+
+// the candidates grouped by the pattern the guess shows each, in order of first appearance
+pub fn partition_by_pattern(candidates: &Words, guess: &Word) -> Vec<(Pattern, Words)> {
+    let mut groups: Vec<(Pattern, Words)> = Vec::new();
+    for c in candidates {
+        let p = pattern(c, guess);
+        match groups.iter_mut().find(|(q, _)| *q == p) {
+            Some((_, g)) => g.push(*c),
+            None => groups.push((p, vec![*c])),
+        }
+    }
+    groups
+}
+
+// each pattern the guess can show as GYB colors with how many candidates show it, most first
+pub fn pattern_counts(candidates: &Words, guess: &Word) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = partition_by_pattern(candidates, guess)
+        .iter()
+        .map(|(p, g)| (pattern_string(*p), g.len()))
+        .collect();
+    counts.sort_by_key(|(_, n)| Reverse(*n));
+    counts
+}
+
+// pattern_counts over the whole list as a table with each pattern's chance
+pub fn opener_breakdown(words: &Words, opener: &Word, precision: usize) -> String {
+    let mut out = String::from("Pattern Remaining Probability\n");
+    for (p, n) in pattern_counts(words, opener) {
+        out.push_str(&format!(
+            "{:<7} {:>9} {:>10.prec$}%\n",
+            p,
+            n,
+            100.0 * n as f64 / words.len() as f64,
+            prec = precision
+        ));
+    }
+    out
+}
+
+// how well an opener's patterns split the list, in one line
+#[derive(Clone, Debug, PartialEq)]
+pub struct OpenerStats {
+    pub patterns: usize,
+    pub entropy: f64,
+    pub largest: usize,
+    pub mean: f64,
+    // share of the words left alone under their pattern, known after the opener
+    pub singletons: f64,
+}
+
+impl fmt::Display for OpenerStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Patterns: {} Entropy: {:.p$} bits Largest: {} Mean: {:.p$} Singletons: {:.p$}%",
+            self.patterns,
+            self.entropy,
+            self.largest,
+            self.mean,
+            100.0 * self.singletons,
+            p = f.precision().unwrap_or(DEFAULT_PRECISION)
+        )
+    }
+}
+
+pub fn opener_pattern_stats(words: &Words, opener: &Word) -> OpenerStats {
+    let sizes = partition_sizes(words.iter().map(|w| pattern(w, opener)));
+    let singles = sizes.iter().filter(|&&n| n == 1).count();
+    OpenerStats {
+        patterns: sizes.len(),
+        entropy: entropy(&sizes),
+        largest: sizes.last().copied().unwrap_or(0),
+        mean: words.len() as f64 / sizes.len().max(1) as f64,
+        singletons: singles as f64 / words.len().max(1) as f64,
+    }
+}
+
+// the candidates bucketed by their letter at pos, letters in alphabetical order
+pub fn group_by_position(candidates: &Words, pos: usize) -> Vec<(char, Words)> {
+    let mut groups: Vec<(char, Words)> = Vec::new();
+    for c in candidates {
+        match groups.iter_mut().find(|(l, _)| *l == c[pos]) {
+            Some((_, g)) => g.push(*c),
+            None => groups.push((c[pos], vec![*c])),
+        }
+    }
+    groups.sort_by_key(|(l, _)| *l);
+    groups
+}
+
+// words with a repeated letter, grouped by how the letters repeat: "2" for abbey,
+// "3" for eerie, "2+2" for llama, "3+2" for mamma. Most common shape first
+pub fn group_by_repeats(words: &Words) -> Vec<(String, Words)> {
+    let mut groups: Vec<(String, Words)> = Vec::new();
+    for w in words {
+        let mut repeats: Vec<u8> = letter_counts(w)
+            .iter()
+            .filter(|n| **n > 1)
+            .copied()
+            .collect();
+        if repeats.is_empty() {
+            continue;
+        }
+        repeats.sort_unstable_by(|a, b| b.cmp(a));
+        let shape: Vec<String> = repeats.iter().map(|n| n.to_string()).collect();
+        let shape = shape.join("+");
+        match groups.iter_mut().find(|(s, _)| *s == shape) {
+            Some((_, g)) => g.push(*w),
+            None => groups.push((shape, vec![*w])),
+        }
+    }
+    groups.sort_by_key(|(_, g)| Reverse(g.len()));
+    groups
+}
+
+// each pattern the guess can show with the candidates behind it, largest group first
+pub fn sorted_partitions(candidates: &Words, guess: &Word) -> Vec<(Pattern, Words)> {
+    let mut groups = partition_by_pattern(candidates, guess);
+    groups.sort_by_key(|(_, g)| Reverse(g.len()));
+    groups
+}
+
+pub fn build_decision_tree(
+    words: &Words,
+    candidates: &Words,
+    strategy: Strategy,
+    max_depth: usize,
+) -> DecisionNode {
+    let node = |guess| DecisionNode {
+        guess,
+        remaining: candidates.len(),
+        children: Vec::new(),
+        leaves: Vec::new(),
+    };
+    if candidates.len() == 1 {
+        return node(Some(candidates[0]));
+    }
+    if max_depth == 0 {
+        return node(None);
+    }
+    match select_guess(words, candidates, strategy) {
+        Some(g) => expand_decision_tree(words, candidates, &g, strategy, max_depth),
+        None => node(None),
+    }
+}
+
+// the tree below a given guess, the strategy choosing from the next turn on
+pub fn expand_decision_tree(
+    words: &Words,
+    candidates: &Words,
+    guess: &Word,
+    strategy: Strategy,
+    max_depth: usize,
+) -> DecisionNode {
+    let guess = *guess;
+    let solved = (NUM_PATTERNS - 1) as Pattern;
+    let mut children = Vec::new();
+    let mut leaves = Vec::new();
+    for (p, g) in partition_by_pattern(candidates, &guess) {
+        if p == solved {
+            continue;
+        }
+        match g[..] {
+            [w] => leaves.push((p, w)),
+            _ => children.push((
+                p,
+                build_decision_tree(words, &g, strategy, max_depth.saturating_sub(1)),
+            )),
+        }
+    }
+    DecisionNode {
+        guess: Some(guess),
+        remaining: candidates.len(),
+        children,
+        leaves,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeStats {
+    // distinct patterns the opener shows, all green included
+    pub branches: usize,
+    // nodes where the strategy stops guessing
+    pub leaves: usize,
+    // most guesses any answer takes
+    pub max_depth: usize,
+}
+
+impl fmt::Display for TreeStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Branches: {} Leaves: {} Max depth: {}",
+            self.branches, self.leaves, self.max_depth
+        )
+    }
+}
+
+// deep enough for any real list, only guards against lists that can't be split
+pub const MAX_TREE_DEPTH: usize = 32;
+
+pub fn tree_stats(words: &Words, opener: &Word, strategy: Strategy) -> TreeStats {
+    fn walk(n: &DecisionNode, depth: usize, stats: &mut TreeStats) {
+        stats.max_depth = stats.max_depth.max(depth);
+        if n.leaves.is_empty() {
+            stats.leaves += n.children.is_empty() as usize;
+        } else {
+            stats.max_depth = stats.max_depth.max(depth + 1);
+            stats.leaves += n.leaves.len();
+        }
+        n.children
+            .iter()
+            .for_each(|(_, c)| walk(c, depth + 1, stats));
+    }
+
+    let root = expand_decision_tree(words, words, opener, strategy, MAX_TREE_DEPTH);
+    let mut stats = TreeStats {
+        branches: root.children.len() + root.leaves.len() + words.contains(opener) as usize,
+        leaves: 0,
+        max_depth: 0,
+    };
+    walk(&root, 1, &mut stats);
+    stats
+}
+
+// the guesses the strategy plays against a known answer, opener first and the answer last
+pub fn simulate(words: &Words, answer: &Word, opener: &Word, strategy: Strategy) -> Vec<Word> {
+    play(words, answer, opener, |c, _| {
+        select_guess(words, c, strategy)
+    })
+}
+
+// a game against the answer from the candidates, choose picking each guess after the
+// opener from the candidates left and the facts so far
+pub fn play(
+    candidates: &Words,
+    answer: &Word,
+    opener: &Word,
+    choose: impl Fn(&Words, &Facts) -> Option<Word>,
+) -> Vec<Word> {
+    let mut candidates = candidates.clone();
+    let mut facts = Facts::new();
+    let mut guess = *opener;
+    let mut played = Vec::new();
+    while played.len() < MAX_TREE_DEPTH {
+        played.push(guess);
+        let feedback = check(answer, &guess, DuplicateMode::Standard);
+        if is_solved(&feedback) {
+            break;
+        }
+        facts.extend(feedback);
+        let p = pattern(answer, &guess);
+        candidates.retain(|c| *c != guess && pattern(c, &guess) == p);
+        match choose(&candidates, &facts) {
+            Some(g) => guess = g,
+            None => break,
+        }
+    }
+    played
+}
+
+// average guesses to solve the candidates, counting this one, once the guess is played
+// and the strategy takes over
+pub fn expected_guesses(
+    words: &Words,
+    candidates: &Words,
+    guess: &Word,
+    strategy: Strategy,
+) -> f64 {
+    let gr = solve_all(words, candidates, guess, strategy);
+    gr.guesses as f64 / gr.num_candidates.max(1) as f64
+}
+
+// how many guesses on average playing this instead of the strategy's choice costs. Zero
+// for the strategy's own pick; negative when its greedy choice isn't the best there is
+pub fn guess_regret(words: &Words, candidates: &Words, played: &Word, strategy: Strategy) -> f64 {
+    match select_guess(words, candidates, strategy) {
+        Some(best) if best != *played => {
+            expected_guesses(words, candidates, played, strategy)
+                - expected_guesses(words, candidates, &best, strategy)
+        }
+        _ => 0.0,
+    }
+}
+
+// one turn of a scripted line: what was played, what the strategy would have played
+// from the same candidates, how many candidates there were and the guess's regret
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineTurn {
+    pub guess: Word,
+    pub best: Option<Word>,
+    pub remaining: usize,
+    pub colors: String,
+    pub regret: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineAnalysis {
+    pub turns: Vec<LineTurn>,
+    // index of the first turn playing something other than the strategy
+    pub divergence: Option<usize>,
+    pub solved: bool,
+}
+
+impl fmt::Display for LineAnalysis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let prec = f.precision().unwrap_or(DEFAULT_PRECISION);
+        for (i, t) in self.turns.iter().enumerate() {
+            write!(
+                f,
+                "{}. {} {} from {} candidates",
+                i + 1,
+                word_str(&t.guess),
+                t.colors,
+                t.remaining
+            )?;
+            match t.best {
+                Some(b) if b == t.guess => write!(f, ", optimal")?,
+                Some(b) => write!(
+                    f,
+                    ", solver plays {}, regret {:+.p$}",
+                    word_str(&b),
+                    t.regret,
+                    p = prec
+                )?,
+                None => {}
+            }
+            if self.divergence == Some(i) {
+                write!(f, " <- first divergence")?;
+            }
+            writeln!(f)?;
+        }
+        write!(f, "{}", if self.solved { "Solved" } else { "Not solved" })
+    }
+}
+
+// plays the scripted guesses against the answer, stopping once one wins, and compares
+// each with the strategy's choice from the candidates left at that turn
+pub fn analyze_line(
+    words: &Words,
+    answer: &Word,
+    line: &[Word],
+    strategy: Strategy,
+) -> LineAnalysis {
+    let mut candidates = words.clone();
+    let mut turns = Vec::new();
+    let mut solved = false;
+    for guess in line {
+        let best = select_guess(words, &candidates, strategy);
+        let feedback = check(answer, guess, DuplicateMode::Standard);
+        turns.push(LineTurn {
+            guess: *guess,
+            best,
+            remaining: candidates.len(),
+            colors: feedback_colors(&feedback),
+            regret: guess_regret(words, &candidates, guess, strategy),
+        });
+        if is_solved(&feedback) {
+            solved = true;
+            break;
+        }
+        let p = pattern(answer, guess);
+        candidates.retain(|c| c != guess && pattern(c, guess) == p);
+    }
+    let divergence = turns
+        .iter()
+        .position(|t| t.best.is_some_and(|b| b != t.guess));
+    LineAnalysis {
+        turns,
+        divergence,
+        solved,
+    }
+}
+
+type GuessCache = Mutex<HashMap<Words, Option<Word>>>;
+
+// solve_all playing each answer on its own thread, the strategy's choice for a set of
+// candidates shared between the games through the cache
+pub fn solve_all_par(
+    words: &Words,
+    candidates: &Words,
+    opener: &Word,
+    strategy: Strategy,
+) -> GuessResult {
+    solve_all_par_until(words, candidates, opener, strategy, None)
+}
+
+// solve_all_par starting no new game once the deadline passes, the result covering
+// only the answers played: num_candidates is how many, the rest are left out
+pub fn solve_all_par_until(
+    words: &Words,
+    candidates: &Words,
+    opener: &Word,
+    strategy: Strategy,
+    deadline: Option<Instant>,
+) -> GuessResult {
+    let expired = AtomicBool::new(false);
+    let cache: GuessCache = Mutex::new(HashMap::new());
+    let choose = |c: &Words, _: &Facts| {
+        if let Some(g) = cache.lock().unwrap().get(c) {
+            return *g;
+        }
+        let g = select_guess(words, c, strategy);
+        cache.lock().unwrap().insert(c.clone(), g);
+        g
+    };
+    let depths: Vec<(Word, usize)> = candidates
+        .par_iter()
+        .filter(|_| {
+            if expired.load(Ordering::Relaxed) || deadline.is_some_and(|d| Instant::now() >= d) {
+                expired.store(true, Ordering::Relaxed);
+                return false;
+            }
+            true
+        })
+        .map(|a| (*a, play(candidates, a, opener, choose).len()))
+        .collect();
+    GuessResult {
+        guess: *opener,
+        guesses: depths.par_iter().map(|(_, d)| d).sum(),
+        num_candidates: depths.len(),
+        partition_sizes: partition_sizes(depths.iter().map(|(a, _)| pattern(a, opener))),
+        depths,
+    }
+}
+
+// the guesses hard mode allows: greens kept in place and yellows played again
+pub fn hard_mode_pool(words: &Words, facts: &Facts) -> Words {
+    words
+        .iter()
+        .filter(|w| {
+            facts.iter().all(|f| match f.feedback {
+                Feedback::Correct => f.position.is_none_or(|p| w[p] == f.letter),
+                Feedback::Used => w.contains(&f.letter),
+                _ => true,
+            })
+        })
+        .copied()
+        .collect()
+}
+
+// solve_all with every guess after the opener taken from the hard mode pool; games with
+// the same candidates left got there the same way, so they share the choice
+pub fn solve_all_hard(words: &Words, opener: &Word, strategy: Strategy) -> GuessResult {
+    let cache: GuessCache = Mutex::new(HashMap::new());
+    let choose = |c: &Words, f: &Facts| {
+        if let Some(g) = cache.lock().unwrap().get(c) {
+            return *g;
+        }
+        let g = select_guess(&hard_mode_pool(words, f), c, strategy);
+        cache.lock().unwrap().insert(c.clone(), g);
+        g
+    };
+    let depths: Vec<(Word, usize)> = words
+        .par_iter()
+        .map(|a| (*a, play(words, a, opener, choose).len()))
+        .collect();
+    GuessResult {
+        guess: *opener,
+        guesses: depths.iter().map(|(_, d)| d).sum(),
+        num_candidates: words.len(),
+        depths,
+        partition_sizes: partition_sizes(words.iter().map(|c| pattern(c, opener))),
+    }
+}
+
+// average guesses hard mode costs the opener over free play
+pub fn hard_mode_penalty(words: &Words, opener: &Word, strategy: Strategy) -> f64 {
+    let avg = |gr: GuessResult| gr.guesses as f64 / gr.num_candidates.max(1) as f64;
+    avg(solve_all_hard(words, opener, strategy)) - avg(solve_all(words, words, opener, strategy))
+}
+
+// GuessResult::distribution summed over threads
+pub fn par_distribution(depths: &[(Word, usize)]) -> Vec<usize> {
+    depths
+        .par_iter()
+        .map(|(_, d)| {
+            let mut dist = vec![0; *d];
+            dist[d - 1] = 1;
+            dist
+        })
+        .reduce_with(|mut a, b| {
+            if a.len() < b.len() {
+                a.resize(b.len(), 0);
+            }
+            a.iter_mut().zip(&b).for_each(|(x, y)| *x += y);
+            a
+        })
+        .unwrap_or_default()
+}
+
+// days from the unix epoch to the first Wordle, 2021-06-19
+pub const FIRST_WORDLE_DAY: u64 = 18797;
+
+// the day's puzzle number and its index into the list, wrapping once the list runs out
+pub fn daily_index(unix_day: u64, len: usize) -> (u64, usize) {
+    let number = unix_day.saturating_sub(FIRST_WORDLE_DAY);
+    (number, (number % len as u64) as usize)
+}
+
+pub fn today() -> u64 {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    secs / 86_400
+}
+
+// every answer with the guesses it takes, easiest first
+// read off the strategy's tree, which plays the same games as simulate with the turns shared
+pub fn rank_answers_by_difficulty(
+    words: &Words,
+    opener: &Word,
+    strategy: Strategy,
+) -> Vec<(Word, usize)> {
+    let mut ranked = solve_all(words, words, opener, strategy).depths;
+    ranked.sort_by_key(|&(w, n)| (n, w));
+    ranked
+}
+
+// the guesses each answer takes following the tree, an answer is solved at the node guessing it
+pub fn tree_depths(root: &DecisionNode) -> Vec<(Word, usize)> {
+    let mut out = Vec::new();
+    visit_depths(root, &mut |w, d| out.push((w, d)));
+    out
+}
+
+// tree_depths handing each answer over as the walk reaches it instead of collecting them
+pub fn visit_depths(root: &DecisionNode, f: &mut impl FnMut(Word, usize)) {
+    fn walk(n: &DecisionNode, depth: usize, f: &mut impl FnMut(Word, usize)) {
+        let below: usize =
+            n.children.iter().map(|(_, c)| c.remaining).sum::<usize>() + n.leaves.len();
+        if let Some(g) = n.guess {
+            if below < n.remaining {
+                f(g, depth);
+            }
+        }
+        n.leaves.iter().for_each(|&(_, w)| f(w, depth + 1));
+        n.children.iter().for_each(|(_, c)| walk(c, depth + 1, f));
+    }
+
+    walk(root, 1, f)
+}
+
+// the turns a game allows before it's lost
+pub const MAX_TURNS: usize = 6;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GameOutcome {
+    Solved,
+    // took more than MAX_TURNS guesses
+    Failed,
+}
+
+impl GameOutcome {
+    pub fn of(guesses: usize) -> GameOutcome {
+        GameOutcome::within(guesses, MAX_TURNS)
+    }
+
+    // the outcome with max_turns allowed instead of MAX_TURNS
+    pub fn within(guesses: usize, max_turns: usize) -> GameOutcome {
+        if guesses <= max_turns {
+            GameOutcome::Solved
+        } else {
+            GameOutcome::Failed
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GameOutcome::Solved => "solved",
+            GameOutcome::Failed => "failed",
+        }
+    }
+}
+
+// how one answer went when played out
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameResult {
+    pub answer: Word,
+    pub guesses: usize,
+    pub outcome: GameOutcome,
+}
+
+impl GameResult {
+    pub fn new(answer: Word, guesses: usize) -> GameResult {
+        GameResult {
+            answer,
+            guesses,
+            outcome: GameOutcome::of(guesses),
+        }
+    }
+
+    pub fn within(answer: Word, guesses: usize, max_turns: usize) -> GameResult {
+        GameResult {
+            answer,
+            guesses,
+            outcome: GameOutcome::within(guesses, max_turns),
+        }
+    }
+
+    // a line of --format jsonl
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"answer\": \"{}\", \"guesses\": {}, \"outcome\": \"{}\"}}",
+            word_str(&self.answer),
+            self.guesses,
+            self.outcome.as_str()
+        )
+    }
+
+    // reads back a to_json line, the outcome is worked out again from the guesses
+    pub fn from_json(line: &str) -> Option<GameResult> {
+        // the text after "key": up to the next comma or brace, quotes stripped
+        let field = |key: &str| -> Option<&str> {
+            let start = line.find(&format!("\"{}\"", key))? + key.len() + 2;
+            let rest = line[start..].trim_start().strip_prefix(':')?;
+            let end = rest.find([',', '}']).unwrap_or(rest.len());
+            Some(rest[..end].trim().trim_matches('"'))
+        };
+        let answer = field("answer")?;
+        if answer.chars().count() != WORD_LENGTH {
+            return None;
+        }
+        let guesses = field("guesses")?.parse().ok()?;
+        Some(GameResult::new(to_array(answer), guesses))
+    }
+}
+
+// the answers a played out opener fails to solve within max_turns, in the result's order
+pub fn unsolvable_answers(gr: &GuessResult, max_turns: usize) -> Vec<GameResult> {
+    gr.depths
+        .iter()
+        .map(|&(w, d)| GameResult::within(w, d, max_turns))
+        .filter(|r| r.outcome == GameOutcome::Failed)
+        .collect()
+}
+
+// answers the two runs took a different number of guesses for, as (answer, a's, b's)
+// in a's order; answers only one run played are left out
+pub fn diff_runs(a: &[GameResult], b: &[GameResult]) -> Vec<(Word, usize, usize)> {
+    let after: HashMap<Word, usize> = b.iter().map(|r| (r.answer, r.guesses)).collect();
+    a.iter()
+        .filter_map(|r| match after.get(&r.answer) {
+            Some(&n) if n != r.guesses => Some((r.answer, r.guesses, n)),
+            _ => None,
+        })
+        .collect()
+}
+
+// the results of a --format jsonl run, lines that don't parse are skipped
+pub fn load_game_results(path: &str) -> io::Result<Vec<GameResult>> {
+    let data = fs::read_to_string(path)?;
+    Ok(data.lines().filter_map(GameResult::from_json).collect())
+}
+
+// how --difficulty prints the answers
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Format {
+    // "word guesses", easiest first
+    #[default]
+    Text,
+    // a GameResult JSON object per line, written as the tree walk reaches each answer
+    Jsonl,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "jsonl" => Ok(Format::Jsonl),
+            _ => Err(format!("unknown format: {}", s)),
+        }
+    }
+}
+
+// the strategy's play over every candidate after opening with the guess
+// guesses is the total over the candidates
+pub fn solve_all(
+    words: &Words,
+    candidates: &Words,
+    opener: &Word,
+    strategy: Strategy,
+) -> GuessResult {
+    let root = expand_decision_tree(words, candidates, opener, strategy, MAX_TREE_DEPTH);
+    let depths = tree_depths(&root);
+    GuessResult {
+        guess: *opener,
+        guesses: depths.iter().map(|(_, d)| d).sum(),
+        num_candidates: candidates.len(),
+        depths,
+        partition_sizes: partition_sizes(candidates.iter().map(|c| pattern(c, opener))),
+    }
+}
+
+// best guess among the words starting with prefix, scored against the candidates
+pub fn best_guess_with_prefix(
+    words: &Words,
+    candidates: &Words,
+    prefix: &str,
+    strategy: Strategy,
+) -> Option<GuessResult> {
+    let pool: Words = words
+        .iter()
+        .filter(|w| word_str(w).starts_with(prefix))
+        .copied()
+        .collect();
+    let guess = select_guess(&pool, candidates, strategy)
+        .filter(|g| pool.contains(g))
+        .or_else(|| {
+            rank_openers(candidates, &pool, strategy)
+                .first()
+                .map(|(w, _)| *w)
+        })?;
+    Some(solve_all(words, candidates, &guess, strategy))
+}
+
+// what --optimize plays the openers out for
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OpenerGoal {
+    // fewest guesses on average
+    Average,
+    // fewest answers taking TAIL_GUESSES or more
+    Tail,
+}
+
+impl FromStr for OpenerGoal {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<OpenerGoal, String> {
+        match s {
+            "avg" | "average" => Ok(OpenerGoal::Average),
+            "tail" => Ok(OpenerGoal::Tail),
+            _ => Err(format!("unknown goal: {}", s)),
+        }
+    }
+}
+
+pub const TAIL_GUESSES: usize = 4;
+
+pub fn tail_count(gr: &GuessResult) -> usize {
+    gr.depths.iter().filter(|(_, d)| *d >= TAIL_GUESSES).count()
+}
+
+// the opener doing best for the goal once solve_all plays it out, earlier openers winning ties
+pub fn best_opener(
+    words: &Words,
+    openers: &Words,
+    strategy: Strategy,
+    goal: OpenerGoal,
+) -> Option<GuessResult> {
+    let key = |gr: &GuessResult| match goal {
+        OpenerGoal::Average => (gr.guesses, tail_count(gr)),
+        OpenerGoal::Tail => (tail_count(gr), gr.guesses),
+    };
+    openers
+        .par_iter()
+        .map(|o| solve_all(words, words, o, strategy))
+        .min_by_key(key)
+}
+
+pub const OPTIMIZE_OPENERS: usize = 10;
+
+// what a --run-report run did and how long each step took, enough to compare runs
+// across commits
+#[derive(Clone, Debug)]
+pub struct RunReport {
+    pub strategy: Strategy,
+    pub opener: Word,
+    pub distribution: Vec<usize>,
+    pub timings: Vec<(&'static str, Duration)>,
+    pub dictionary_hash: u64,
+}
+
+impl RunReport {
+    pub fn run(words: &Words, strategy: Strategy, first_guess: Option<Word>) -> Option<RunReport> {
+        let mut timings = Vec::new();
+        let t = Instant::now();
+        let opener = first_guess.or_else(|| select_guess(words, words, strategy))?;
+        timings.push(("opener", t.elapsed()));
+        let t = Instant::now();
+        let distribution = solve_all(words, words, &opener, strategy).distribution();
+        timings.push(("solve_all", t.elapsed()));
+        Some(RunReport {
+            strategy,
+            opener,
+            distribution,
+            timings,
+            dictionary_hash: dictionary_hash(words),
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        let dist: Vec<String> = self.distribution.iter().map(|n| n.to_string()).collect();
+        let timings: Vec<String> = self
+            .timings
+            .iter()
+            .map(|(step, d)| format!("{:?}: {:.6}", step, d.as_secs_f64()))
+            .collect();
+        format!(
+            "{{\"strategy\": \"{:?}\", \"opener\": \"{}\", \"distribution\": [{}], \"timings\": {{{}}}, \"dictionary_hash\": \"{:016x}\"}}",
+            self.strategy,
+            word_str(&self.opener),
+            dist.join(", "),
+            timings.join(", "),
+            self.dictionary_hash
+        )
+    }
+}
+
+impl fmt::Display for RunReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Strategy: {:?}", self.strategy)?;
+        writeln!(f, "Opener: {}", word_str(&self.opener))?;
+        writeln!(
+            f,
+            "Distribution: {}",
+            format_distribution(&self.distribution)
+        )?;
+        for (step, d) in &self.timings {
+            writeln!(f, "{}: {:.2?}", step, d)?;
+        }
+        write!(f, "Dictionary: {:016x}", self.dictionary_hash)
+    }
+}
+
+// FNV-1a over the words in order, the same list always hashing the same
+pub fn dictionary_hash(words: &Words) -> u64 {
+    words
+        .iter()
+        .flat_map(|w| w.iter().chain(std::iter::once(&'\n')))
+        .fold(0xcbf2_9ce4_8422_2325, |h, &c| {
+            (h ^ c as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        })
+}
+
+pub const REPORT_OPENERS: usize = 5;
+
+// Markdown table of the best scoring openers played out over the whole list
+pub fn opener_report(words: &Words, strategy: Strategy, precision: usize) -> String {
+    let mut out = String::from("| Opener | Avg guesses | Worst case | Solved in 6 |\n");
+    out.push_str("|---|---|---|---|\n");
+    for (opener, _) in rank_openers(words, words, strategy)
+        .iter()
+        .take(REPORT_OPENERS)
+    {
+        let gr = solve_all(words, words, opener, strategy);
+        let dist = gr.distribution();
+        let within: usize = dist.iter().take(6).sum();
+        out.push_str(&format!(
+            "| {} | {:.p$} | {} | {:.p$}% |\n",
+            gr.word_str(),
+            gr.guesses as f64 / gr.num_candidates as f64,
+            dist.len(),
+            100.0 * within as f64 / gr.num_candidates as f64,
+            p = precision
+        ));
+    }
+    out
+}
+
+// GraphViz digraph of the tree, edges labeled with the colors leading to each node;
+// single candidates are listed in their parent's label with the colors leading to them
+pub fn tree_dot(root: &DecisionNode) -> String {
+    fn visit(n: &DecisionNode, id: &mut usize, out: &mut String) -> usize {
+        let me = *id;
+        *id += 1;
+        let mut label = match &n.guess {
+            Some(w) => format!("{}\\n{}", word_str(w), n.remaining),
+            None => format!("{} left", n.remaining),
+        };
+        for (p, w) in &n.leaves {
+            label.push_str(&format!("\\n{} {}", pattern_string(*p), word_str(w)));
+        }
+        out.push_str(&format!("  n{} [label=\"{}\"];\n", me, label));
+        for (p, child) in &n.children {
+            let c = visit(child, id, out);
+            out.push_str(&format!(
+                "  n{} -> n{} [label=\"{}\"];\n",
+                me,
+                c,
+                pattern_string(*p)
+            ));
+        }
+        me
+    }
+
+    let mut out = String::from("digraph {\n");
+    visit(root, &mut 0, &mut out);
+    out.push_str("}\n");
+    out
+}
+
+// distinct letters of the guess no fact has tested yet
+pub fn new_letter_count(facts: &Facts, guess: &Word) -> usize {
+    let mut seen = [false; NUM_CHARS];
+    facts
+        .iter()
+        .filter_map(|f| letter_index(f.letter))
+        .for_each(|i| seen[i] = true);
+    guess
+        .iter()
+        .filter_map(|&c| letter_index(c))
+        .filter(|&i| !mem::replace(&mut seen[i], true))
+        .count()
+}
+
+// most new letters first, keeping the ranking's order among equals
+pub fn sort_by_new_letters(ranked: &mut [(Word, GuessEval)], facts: &Facts) {
+    ranked.sort_by_key(|(w, _)| Reverse(new_letter_count(facts, w)));
+}
+
+// whether the guess plays every known green where it was found, as hard mode demands
+pub fn keeps_greens(facts: &Facts, guess: &Word) -> bool {
+    facts
+        .iter()
+        .filter(|f| f.feedback == Feedback::Correct)
+        .all(|f| f.position.is_none_or(|p| guess[p] == f.letter))
+}
+
+// guesses keeping the known greens first among those scoring the same, otherwise the
+// ranking's order; unlike hard mode a guess moving a green can still rank higher
+pub fn prefer_green_keepers(ranked: &mut [(Word, GuessEval)], facts: &Facts, strategy: Strategy) {
+    for tied in ranked.chunk_by_mut(|a, b| a.1.cost(strategy) == b.1.cost(strategy)) {
+        tied.sort_by_key(|(w, _)| !keeps_greens(facts, w));
+    }
+}
+
+// the strategy's choice next to the given guess, each with its score under the strategy
+pub fn compare_guess(
+    words: &Words,
+    candidates: &Words,
+    guess: &Word,
+    strategy: Strategy,
+    precision: usize,
+) -> String {
+    let line = |label: &str, w: &Word| {
+        let eval = evaluate_guess(candidates, w);
+        let score = match strategy {
+            Strategy::Minimax => eval.worst.to_string(),
+            _ => format!("{:.*}", precision, eval.score(strategy)),
+        };
+        format!(
+            "{} {} {:?}: {} Worst case: {} left",
+            label,
+            word_str(w),
+            strategy,
+            score,
+            eval.worst
+        )
+    };
+    match select_guess(words, candidates, strategy) {
+        Some(chosen) => format!("{}\n{}", line("Chosen:", &chosen), line("Yours: ", guess)),
+        None => String::from("No candidates left"),
+    }
+}
+
+// decimal places of averages, entropies and percentages unless --precision says
+// otherwise; the Display impls take it from the format string, e.g. {:.2}
+pub const DEFAULT_PRECISION: usize = 4;
+
+// set WORDLE_LOG=1 to trace the search on stderr, checked once per run
+fn log_enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| env::var("WORDLE_LOG").is_ok_and(|v| v == "1"))
+}
+
+// exhaustive search for the word which minimizes the number of guesses, None when no
+// word matches the facts
+pub fn best_guess(words: &Words, facts: &Facts) -> Option<GuessResult> {
+    best_guess_capped(words, facts, None)
+}
+
+// best_guess recursing into at most cap guesses per level, None for all of them
+pub fn best_guess_capped(words: &Words, facts: &Facts, cap: Option<usize>) -> Option<GuessResult> {
+    if !words
+        .iter()
+        .any(|w| matches(w, facts, DuplicateMode::Standard))
+    {
+        return None;
+    }
+    Some(search(words, facts, 0, cap))
+}
+
+fn search(words: &Words, facts: &Facts, depth: usize, cap: Option<usize>) -> GuessResult {
+    let gr = search_level(words, facts, depth, cap);
+    if log_enabled() {
+        eprintln!(
+            "{:indent$}depth: {} candidates: {} guess: {}",
+            "",
+            depth,
+            gr.num_candidates,
+            gr.word_str(),
+            indent = depth * 2
+        );
+    }
+    gr
+}
+
+// whether the guess separates the candidates at all; among candidate guesses only
+// repeated words in the list can fail this, and recursing on them would never end
+fn splits(candidates: &Words, guess: &Word) -> bool {
+    let first = pattern(&candidates[0], guess);
+    candidates.iter().any(|c| pattern(c, guess) != first)
+}
+
+// the guesses worth searching, those splitting the most ways first when they're capped
+fn cap_branches<T: Copy>(
+    guesses: Vec<T>,
+    cap: Option<usize>,
+    groups: impl Fn(&T) -> usize,
+) -> Vec<T> {
+    match cap {
+        Some(k) if guesses.len() > k => {
+            let mut scored: Vec<(T, usize)> = guesses.iter().map(|g| (*g, groups(g))).collect();
+            scored.sort_by_key(|(_, n)| Reverse(*n));
+            scored.into_iter().take(k).map(|(g, _)| g).collect()
+        }
+        _ => guesses,
+    }
+}
+
+// guesses that split the candidates, only the first of those splitting them into the
+// same groups: the groups are searched alike, so the later ones can only tie with it
+fn branch_guesses(candidates: &Words, cap: Option<usize>) -> Words {
+    let mut seen = HashSet::new();
+    let distinct = candidates
+        .iter()
+        .copied()
+        .filter(|g| splits(candidates, g) && seen.insert(partition_signature(candidates, g)))
+        .collect();
+    cap_branches(distinct, cap, |g| evaluate_guess(candidates, g).groups)
+}
+
+// result for candidates no guess can tell apart, i.e. copies of the same word
+fn indistinguishable(candidates: &Words) -> GuessResult {
+    GuessResult {
+        guess: candidates[0],
+        guesses: 1,
+        num_candidates: candidates.len(),
+        depths: candidates.iter().map(|w| (*w, 1)).collect(),
+        partition_sizes: vec![candidates.len()],
+    }
+}
+
+// the facts always leave a candidate: best_guess checks for one up front and each
+// level's facts come from checking one of its candidates
+fn search_level(words: &Words, facts: &Facts, depth: usize, cap: Option<usize>) -> GuessResult {
+    let candidates: Words = filter_words(words, facts, DuplicateMode::Standard);
+    if candidates.len() == 1 {
+        GuessResult {
+            guess: candidates[0],
+            guesses: 1,
+            num_candidates: candidates.len(),
+            depths: vec![(candidates[0], 1)],
+            partition_sizes: vec![1],
+        }
+    } else {
+        branch_guesses(&candidates, cap)
+            .par_iter()
+            .map(|g: &Word| {
+                let mut gs = 0;
+                let mut depths = Vec::new();
+                candidates.iter().for_each(|w: &Word| {
+                    let mut new_facts: Facts = check(w, g, DuplicateMode::Standard);
+                    let mut prev_facts: Facts = facts.to_vec();
+                    new_facts.append(&mut prev_facts);
+
+                    let next = search(&candidates, &new_facts, depth + 1, cap);
+                    gs += next.guesses;
+                    depths.push((*w, depth_after(g, w, &next)));
+                });
+
+                GuessResult {
+                    guess: *g,
+                    guesses: 1 + gs,
+                    num_candidates: candidates.len(),
+                    depths,
+                    partition_sizes: partition_sizes(candidates.iter().map(|w| pattern(w, g))),
+                }
+            })
+            .reduce_with(|best_guess, gr| {
+                if gr.guesses < best_guess.guesses {
+                    gr
+                } else {
+                    best_guess
+                }
+            })
+            .unwrap_or_else(|| indistinguishable(&candidates))
+    }
+}
+
+// exhaustive search using best_guess, will return the number of guesses for each word
+pub fn solve(words: &Words, guesses: &Words) -> Vec<GuessResult> {
+    guesses
+        .iter()
+        .map(|g| {
+            let mut gs = 0;
+            let mut depths = Vec::new();
+            words.iter().for_each(|w| {
+                let fs = check(w, g, DuplicateMode::Standard);
+                let next = search(words, &fs, 0, None);
+                gs += next.guesses;
+                depths.push((*w, depth_after(g, w, &next)));
+            });
+
+            GuessResult {
+                guess: *g,
+                guesses: 1 + gs,
+                num_candidates: guesses.len(),
+                depths,
+                partition_sizes: partition_sizes(words.iter().map(|w| pattern(w, g))),
+            }
+        })
+        .collect()
+}
+
+type SearchCache = Mutex<HashMap<Vec<usize>, GuessResult>>;
+
+// same search as best_guess over indices into the table's words, grouping candidates
+// by their precomputed pattern instead of re-checking and re-filtering facts; past the
+// deadline only the guess splitting the most ways is searched at each level
+fn search_shared(
+    table: &PatternTable,
+    candidates: &[usize],
+    cap: Option<usize>,
+    deadline: Option<Instant>,
+    cache: &SearchCache,
+) -> GuessResult {
+    let words = table.words;
+    if candidates.len() == 1 {
+        let w = words[candidates[0]];
+        return GuessResult {
+            guess: w,
+            guesses: 1,
+            num_candidates: 1,
+            depths: vec![(w, 1)],
+            partition_sizes: vec![1],
+        };
+    }
+    if let Some(gr) = cache.lock().unwrap().get(candidates) {
+        return gr.clone();
+    }
+
+    let splitting: Vec<usize> = candidates
+        .iter()
+        .copied()
+        .filter(|&g| {
+            let first = table.get(g, candidates[0]);
+            candidates.iter().any(|&a| table.get(g, a) != first)
+        })
+        .collect();
+    let cap = match deadline {
+        Some(d) if Instant::now() >= d => Some(1),
+        _ => cap,
+    };
+    let gr = cap_branches(splitting, cap, |&g| {
+        partition_counts(table, g, candidates)
+            .iter()
+            .filter(|&&n| n > 0)
+            .count()
+    })
+    .par_iter()
+    .map(|&g| shared_result(table, candidates, g, cap, deadline, cache))
+    .reduce_with(|best_guess, gr| {
+        if gr.guesses < best_guess.guesses {
+            gr
+        } else {
+            best_guess
+        }
+    })
+    .unwrap();
+
+    cache
+        .lock()
+        .unwrap()
+        .insert(candidates.to_vec(), gr.clone());
+    gr
+}
+
+// the guess played against the candidates, searching on from each pattern it shows
+fn shared_result(
+    table: &PatternTable,
+    candidates: &[usize],
+    g: usize,
+    cap: Option<usize>,
+    deadline: Option<Instant>,
+    cache: &SearchCache,
+) -> GuessResult {
+    let words = table.words;
+    let patterns: Vec<usize> = candidates
+        .iter()
+        .map(|&a| table.get(g, a) as usize)
+        .collect();
+    let mut next: Vec<Option<GuessResult>> = vec![None; NUM_PATTERNS];
+    for &p in &patterns {
+        if next[p].is_none() {
+            let group: Vec<usize> = candidates
+                .iter()
+                .zip(&patterns)
+                .filter(|(_, &q)| q == p)
+                .map(|(&a, _)| a)
+                .collect();
+            next[p] = Some(search_shared(table, &group, cap, deadline, cache));
+        }
+    }
+
+    let mut gs = 0;
+    let mut depths = Vec::new();
+    candidates.iter().zip(&patterns).for_each(|(&a, &p)| {
+        let n = next[p].as_ref().unwrap();
+        gs += n.guesses;
+        depths.push((words[a], depth_after(&words[g], &words[a], n)));
+    });
+
+    GuessResult {
+        guess: words[g],
+        guesses: 1 + gs,
+        num_candidates: candidates.len(),
+        depths,
+        partition_sizes: partition_sizes(patterns.iter().map(|&p| p as Pattern)),
+    }
+}
+
+// best_guess over the shared table, same result but much faster on larger sets
+pub fn best_guess_shared(words: &Words, facts: &Facts, cap: Option<usize>) -> GuessResult {
+    let candidates = filter_words(words, facts, DuplicateMode::Standard);
+    let table = PatternTable::new(&candidates, false);
+    let all: Vec<usize> = (0..candidates.len()).collect();
+    search_shared(&table, &all, cap, None, &Mutex::new(HashMap::new()))
+}
+
+// best_guess_shared within a time budget: guesses are tried most promising first by
+// the strategy and, once the budget is spent, the rest are skipped for the best result
+// so far and the searches still running finish greedily
+pub fn best_guess_timed(
+    words: &Words,
+    facts: &Facts,
+    strategy: Strategy,
+    budget: Duration,
+    cap: Option<usize>,
+) -> GuessResult {
+    let deadline = Instant::now() + budget;
+    let expired = AtomicBool::new(false);
+    let candidates = filter_words(words, facts, DuplicateMode::Standard);
+    let table = PatternTable::new(&candidates, false);
+    let all: Vec<usize> = (0..candidates.len()).collect();
+    let cache: SearchCache = Mutex::new(HashMap::new());
+    if all.len() == 1 {
+        return search_shared(&table, &all, cap, Some(deadline), &cache);
+    }
+
+    let costs: Vec<f64> = all
+        .iter()
+        .map(|&g| GuessEval::from_counts(&partition_counts(&table, g, &all)).cost(strategy))
+        .collect();
+    let mut order = all.clone();
+    order.sort_by(|&a, &b| costs[a].partial_cmp(&costs[b]).unwrap());
+    let ranked: Vec<(usize, usize)> = order.into_iter().enumerate().collect();
+    ranked
+        .par_iter()
+        .filter(|&&(i, _)| {
+            // the most promising guess is always searched so there's a result
+            if i > 0 && (expired.load(Ordering::Relaxed) || Instant::now() >= deadline) {
+                expired.store(true, Ordering::Relaxed);
+                return false;
+            }
+            true
+        })
+        .map(|&(i, g)| {
+            let gr = shared_result(&table, &all, g, cap, Some(deadline), &cache);
+            (i, gr)
+        })
+        .reduce_with(|best, next| {
+            if (next.1.guesses, next.0) < (best.1.guesses, best.0) {
+                next
+            } else {
+                best
+            }
+        })
+        .map(|(_, gr)| gr)
+        .unwrap()
+}
+
+// solve with a single pattern table and search cache shared across every guess
+pub fn solve_shared(words: &Words, guesses: &Words) -> Vec<GuessResult> {
+    solve_shared_each(words, guesses, None, |_| false, |_| {})
+}
+
+// solve_shared skipping some guesses and reporting each result as soon as it is done
+pub fn solve_shared_each(
+    words: &Words,
+    guesses: &Words,
+    cap: Option<usize>,
+    skip: impl Fn(&Word) -> bool,
+    mut done: impl FnMut(&GuessResult),
+) -> Vec<GuessResult> {
+    let table = PatternTable::new(words, false);
+    let cache: SearchCache = Mutex::new(HashMap::new());
+    guesses
+        .iter()
+        .filter(|g| !skip(g))
+        .map(|g| {
+            let patterns: Vec<Pattern> = words.iter().map(|w| pattern(w, g)).collect();
+            let mut gs = 0;
+            let mut depths = Vec::new();
+            words.iter().zip(&patterns).for_each(|(w, &p)| {
+                let group: Vec<usize> = (0..words.len()).filter(|&i| patterns[i] == p).collect();
+                let next = search_shared(&table, &group, cap, None, &cache);
+                gs += next.guesses;
+                depths.push((*w, depth_after(g, w, &next)));
+            });
+
+            let gr = GuessResult {
+                guess: *g,
+                guesses: 1 + gs,
+                num_candidates: guesses.len(),
+                depths,
+                partition_sizes: partition_sizes(patterns.iter().copied()),
+            };
+            done(&gr);
+            gr
+        })
+        .collect()
+}
+
+// results recorded by an earlier --checkpoint run, missing file means none
+pub fn load_checkpoint(path: &str) -> io::Result<Vec<GuessResult>> {
+    match fs::read_to_string(path) {
+        Ok(data) => Ok(data.lines().filter_map(GuessResult::from_csv).collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e),
+    }
+}
+
+// progress of a long opener search: the best opener found so far and every opener
+// already played out, enough to pick up where an interrupted run stopped
+#[derive(Clone, Debug, Default)]
+pub struct OpenerSearch {
+    pub best: Option<GuessResult>,
+    pub evaluated: Vec<GuessResult>,
+}
+
+impl OpenerSearch {
+    // "# best: word,guesses,n" then a to_csv row per evaluated opener
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        if let Some(best) = &self.best {
+            out.push_str(&format!("# best: {}\n", best.to_csv()));
+        }
+        self.evaluated
+            .iter()
+            .for_each(|gr| out.push_str(&format!("{}\n", gr.to_csv())));
+        out
+    }
+
+    // rows that don't parse are skipped, those openers are searched again
+    pub fn from_text(text: &str) -> OpenerSearch {
+        let mut search = OpenerSearch::default();
+        for line in text.lines() {
+            match line.strip_prefix("# best: ") {
+                Some(best) => search.best = GuessResult::from_csv(best),
+                None => search.evaluated.extend(GuessResult::from_csv(line)),
+            }
+        }
+        search
+    }
+
+    pub fn record(&mut self, gr: &GuessResult) {
+        if self.best.as_ref().is_none_or(|b| gr.guesses < b.guesses) {
+            self.best = Some(gr.clone());
+        }
+        self.evaluated.push(gr.clone());
+    }
+}
+
+// a missing file is a search not started yet
+pub fn load_opener_search(path: &str) -> io::Result<OpenerSearch> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(OpenerSearch::from_text(&text)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(OpenerSearch::default()),
+        Err(e) => Err(e),
+    }
+}
+
+// written next to the file and renamed over it, so a run killed mid-write leaves the
+// previous state instead of half a file
+pub fn save_opener_search(path: &str, search: &OpenerSearch) -> io::Result<()> {
+    let tmp = format!("{}.tmp", path);
+    fs::write(&tmp, search.to_text())?;
+    fs::rename(&tmp, path)
+}
+
+// the exhaustive search's best opener, played out one opener at a time with the
+// openers already in search skipped; save is handed the progress after each one.
+// Ties go to the opener evaluated first, so resuming picks what one run would have
+pub fn search_openers(
+    words: &Words,
+    openers: &Words,
+    cap: Option<usize>,
+    search: &mut OpenerSearch,
+    mut save: impl FnMut(&OpenerSearch),
+) -> Option<GuessResult> {
+    let done: HashSet<Word> = search.evaluated.iter().map(|gr| gr.guess).collect();
+    solve_shared_each(
+        words,
+        openers,
+        cap,
+        |g| done.contains(g),
+        |gr| {
+            search.record(gr);
+            save(search);
+        },
+    );
+    search.best.clone()
+}
+
+// reproducible order for solve output: fewest guesses first, then alphabetical or,
+// given the list, by where the guess appears in it
+pub fn sort_results(results: &mut [GuessResult], order: Option<&Words>) {
+    match order {
+        Some(words) => {
+            let index: HashMap<Word, usize> =
+                words.iter().enumerate().map(|(i, w)| (*w, i)).collect();
+            results.sort_by_key(|gr| (gr.guesses, index.get(&gr.guess).copied()));
+        }
+        None => results.sort_by_key(|gr| (gr.guesses, gr.guess)),
+    }
+}
+
+// splitmix64, enough to draw reproducible samples without a dependency
+pub struct SplitMix64(pub u64);
+
+impl SplitMix64 {
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+// m of the words drawn without replacement, the same ones for the same seed
+pub fn sample_words(words: &Words, m: usize, seed: u64) -> Words {
+    let mut rng = SplitMix64(seed);
+    let mut sample = words.clone();
+    let m = m.min(sample.len());
+    for i in 0..m {
+        let j = i + rng.below(sample.len() - i);
+        sample.swap(i, j);
+    }
+    sample.truncate(m);
+    sample
+}
+
+// a seeded random m of the guess pool, kept in list order so ties break as they do
+// over the whole pool; all of it once m covers the pool
+pub fn sample_pool(words: &Words, m: usize, seed: u64) -> Words {
+    let mut rng = SplitMix64(seed);
+    let mut picks: Vec<usize> = (0..words.len()).collect();
+    let m = m.min(picks.len());
+    for i in 0..m {
+        let j = i + rng.below(picks.len() - i);
+        picks.swap(i, j);
+    }
+    picks.truncate(m);
+    picks.sort_unstable();
+    picks.iter().map(|&i| words[i]).collect()
+}
+
+// select_guess scoring only a sample of the pool, turn picks a fresh sample each turn
+pub fn select_guess_sampled(
+    words: &Words,
+    candidates: &Words,
+    strategy: Strategy,
+    (m, seed): (usize, u64),
+    turn: usize,
+) -> Option<Word> {
+    let pool = sample_pool(words, m, seed.wrapping_add(turn as u64));
+    select_guess(&pool, candidates, strategy)
+}
+
+// candidates left after the guess summed over the answers
+pub fn greedy_score(words: &Words, guess: &Word, answers: &Words) -> usize {
+    answers
+        .par_iter()
+        .map(|w| {
+            let facts = check(w, guess, DuplicateMode::Standard);
+            filter_words(words, &facts, DuplicateMode::Standard).len()
+        })
+        .sum()
+}
+
+// Greedy algorithm that finds the word that maximizes the most information gain
+// (Reduce the number of remaining possibilities)
+// scores the first n words, over a sample of the answers scaled up to the list when given
+pub fn greedy(words: &Words, n: usize, sample: Option<usize>, seed: u64) -> Vec<(Word, usize)> {
+    let answers = match sample {
+        Some(m) => sample_words(words, m, seed),
+        None => words.clone(),
+    };
+    let mut results = Vec::new();
+    words.iter().take(n).for_each(|guess| {
+        let score = greedy_score(words, guess, &answers);
+        let res = score * words.len() / answers.len().max(1);
+
+        results.push((*guess, res));
+    });
+    results
+}
+
+// number of words containing each letter
+pub fn letter_frequencies(words: &Words, config: &Config) -> [usize; NUM_CHARS] {
+    let mut freqs = [0; NUM_CHARS];
+    for w in words {
+        for c in &config.alphabet {
+            if let Some(i) = letter_index(*c).filter(|_| w.contains(c)) {
+                freqs[i] += 1;
+            }
+        }
+    }
+    freqs
+}
+
+// letters of the alphabet no word uses, openers gain nothing from them
+pub fn unused_letters(words: &Words, config: &Config) -> Vec<char> {
+    let freqs = letter_frequencies(words, config);
+    config
+        .alphabet
+        .iter()
+        .filter(|c| letter_index(**c).is_some_and(|i| freqs[i] == 0))
+        .copied()
+        .collect()
+}
+
+// cheap heuristic: total frequency of the distinct letters in the guess
+pub fn frequency_score(freqs: &[usize; NUM_CHARS], guess: &Word, config: &Config) -> usize {
+    config
+        .alphabet
+        .iter()
+        .filter(|c| guess.contains(c))
+        .filter_map(|&c| letter_index(c))
+        .map(|i| freqs[i])
+        .sum()
+}
+
+pub const COMMON_LETTERS: usize = 5;
+
+// how many of the COMMON_LETTERS most frequent letters the guess contains
+pub fn common_letter_coverage(freqs: &[usize; NUM_CHARS], guess: &Word, config: &Config) -> usize {
+    let mut letters: Vec<usize> = config
+        .alphabet
+        .iter()
+        .filter_map(|&c| letter_index(c))
+        .collect();
+    letters.sort_by_key(|&i| Reverse(freqs[i]));
+    letters
+        .iter()
+        .take(COMMON_LETTERS)
+        .filter(|&&i| guess.contains(&ASCII_LOWER[i]))
+        .count()
+}
+
+// quick orderings of the list that skip scoring against the candidates
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RankBy {
+    Coverage,
+}
+
+impl FromStr for RankBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<RankBy, String> {
+        match s {
+            "coverage" => Ok(RankBy::Coverage),
+            _ => Err(format!("unknown ranking: {}", s)),
+        }
+    }
+}
+
+// indices of the k words with the best frequency score, in list order
+pub fn frequency_pool(words: &Words, k: usize, config: &Config) -> Vec<usize> {
+    let freqs = letter_frequencies(words, config);
+    let mut pool: Vec<usize> = (0..words.len()).collect();
+    pool.sort_by_key(|&i| Reverse(frequency_score(&freqs, &words[i], config)));
+    pool.truncate(k);
+    pool.sort_unstable();
+    pool
+}
+
+// number of times each letter appears at each position, one row per letter
+pub fn positional_frequencies(words: &Words) -> [[usize; WORD_LENGTH]; NUM_CHARS] {
+    let mut freqs = [[0; WORD_LENGTH]; NUM_CHARS];
+    for w in words {
+        for (j, c) in w.iter().enumerate() {
+            if let Some(i) = letter_index(*c) {
+                freqs[i][j] += 1;
+            }
+        }
+    }
+    freqs
+}
+
+// CSV heatmap of the positional frequencies: letter,pos0,...,pos4
+pub fn heatmap_csv(freqs: &[[usize; WORD_LENGTH]; NUM_CHARS], config: &Config) -> String {
+    let mut csv = String::from("letter");
+    for j in 0..WORD_LENGTH {
+        csv.push_str(&format!(",pos{}", j));
+    }
+    csv.push('\n');
+    for (i, row) in freqs.iter().enumerate() {
+        if !config.alphabet.contains(&ASCII_LOWER[i]) {
+            continue;
+        }
+        csv.push(ASCII_LOWER[i]);
+        row.iter().for_each(|n| csv.push_str(&format!(",{}", n)));
+        csv.push('\n');
+    }
+    csv
+}
+
+pub fn factify(correct: &[(char, usize)], used: &[(char, usize)], not_used: &str) -> Facts {
+    let mut facts = Vec::new();
+    correct.iter().for_each(|f| {
+        facts.push(Fact {
+            letter: f.0,
+            position: Some(f.1),
+            feedback: Feedback::Correct,
+        });
+    });
+
+    used.iter().for_each(|f| {
+        facts.push(Fact {
+            letter: f.0,
+            position: Some(f.1),
+            feedback: Feedback::Used,
+        });
+    });
+
+    not_used.chars().collect::<Vec<_>>().iter().for_each(|c| {
+        facts.push(Fact {
+            letter: *c,
+            position: None,
+            feedback: Feedback::NotUsed,
+        });
+    });
+
+    facts
+}
+
+// facts from known greens, e.g. ".l..." or "_l___", and letters known to be absent;
+// factify without yellows
+pub fn greens_facts(template: &str, absent: &str) -> Result<Facts, String> {
+    let template = template.trim().to_lowercase();
+    if template.chars().count() != WORD_LENGTH {
+        return Err(format!(
+            "greens need {} positions, got {:?}",
+            WORD_LENGTH, template
+        ));
+    }
+    let correct: Placements = template
+        .chars()
+        .enumerate()
+        .filter(|(_, c)| c.is_ascii_lowercase())
+        .map(|(i, c)| (c, i))
+        .collect();
+    Ok(factify(&correct, &[], &absent.trim().to_lowercase()))
+}
+
+// a known letter count, e.g. exactly two e's; not a color so it isn't a Fact
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExactCount {
+    pub letter: char,
+    pub count: u8,
+}
+
+impl ExactCount {
+    pub fn matches(&self, w: &Word) -> bool {
+        w.iter().filter(|c| **c == self.letter).count() == self.count as usize
+    }
+}
+
+// "e:2" for exactly two e's
+pub fn parse_exact(value: &str) -> Result<ExactCount, String> {
+    let bad = || format!("--exact takes letter:count, e.g. e:2, got {:?}", value);
+    let (letter, count) = value.trim().split_once(':').ok_or_else(bad)?;
+    let mut letter = letter.chars();
+    let (Some(l), None) = (letter.next(), letter.next()) else {
+        return Err(bad());
+    };
+    let l = l.to_ascii_lowercase();
+    match count.parse() {
+        Ok(n) if l.is_ascii_lowercase() && n as usize <= WORD_LENGTH => Ok(ExactCount {
+            letter: l,
+            count: n,
+        }),
+        _ => Err(bad()),
+    }
+}
+
+// factify's facts built up one at a time, e.g.
+// FactsBuilder::new().correct('l', 1).present('l', 3).absent('c').build()
+#[derive(Clone, Debug, Default)]
+pub struct FactsBuilder {
+    pub correct: Placements,
+    pub present: Placements,
+    pub absent: String,
+}
+
+impl FactsBuilder {
+    pub fn new() -> FactsBuilder {
+        FactsBuilder::default()
+    }
+
+    pub fn correct(mut self, letter: char, pos: usize) -> Self {
+        if !self.correct.contains(&(letter, pos)) {
+            self.correct.push((letter, pos));
+        }
+        self
+    }
+
+    pub fn present(mut self, letter: char, pos: usize) -> Self {
+        if !self.present.contains(&(letter, pos)) {
+            self.present.push((letter, pos));
+        }
+        self
+    }
+
+    pub fn absent(mut self, letter: char) -> Self {
+        if !self.absent.contains(letter) {
+            self.absent.push(letter);
+        }
+        self
+    }
+
+    // in factify's order: correct, then present, then absent letters
+    pub fn build(&self) -> Facts {
+        factify(&self.correct, &self.present, &self.absent)
+    }
+}
+
+// inverse of factify, only the letters of NotUsed facts are kept
+pub fn defactify(facts: &Facts) -> (Placements, Placements, String) {
+    let mut correct = Vec::new();
+    let mut used = Vec::new();
+    let mut not_used = String::new();
+    facts.iter().for_each(|f| match f.feedback {
+        Feedback::Correct => correct.extend(f.position.map(|p| (f.letter, p))),
+        Feedback::Used => used.extend(f.position.map(|p| (f.letter, p))),
+        Feedback::NotUsed => not_used.push(f.letter),
+        Feedback::Unknown => {}
+    });
+    (correct, used, not_used)
+}
+
+// the letters a puzzle's words use, by default English a to z; words are always
+// WORD_LENGTH letters. Config::default().alphabet(parse_alphabet("abc...")?)
+#[derive(Clone, Debug)]
+pub struct Config {
+    // a subset of a-z; the per letter tables keep their a-z slots so only these
+    // letters' slots are filled
+    pub alphabet: Vec<char>,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            alphabet: ASCII_LOWER.to_vec(),
+        }
+    }
+}
+
+impl Config {
+    pub fn alphabet(mut self, letters: Vec<char>) -> Config {
+        self.alphabet = letters;
+        self
+    }
+}
+
+pub fn parse_alphabet(value: &str) -> Result<Vec<char>, String> {
+    let mut letters: Vec<char> = Vec::new();
+    for c in value.trim().to_lowercase().chars() {
+        if !c.is_ascii_lowercase() {
+            return Err(format!("--alphabet takes letters a to z, got {:?}", c));
+        }
+        if !letters.contains(&c) {
+            letters.push(c);
+        }
+    }
+    letters.sort_unstable();
+    Ok(letters)
+}
+
+// where the words' separators sit: Ok with the first word's positions, or the words
+// putting them elsewhere
+pub fn separator_positions(words: &Words) -> Result<Vec<usize>, Vec<String>> {
+    let positions =
+        |w: &Word| -> Vec<usize> { (0..WORD_LENGTH).filter(|i| w[*i] == SEPARATOR).collect() };
+    let Some(first) = words.first().map(positions) else {
+        return Ok(Vec::new());
+    };
+    let odd: Vec<String> = words
+        .iter()
+        .filter(|w| positions(w) != first)
+        .map(word_str)
+        .collect();
+    if odd.is_empty() {
+        Ok(first)
+    } else {
+        Err(odd)
+    }
+}
+
+// words using a letter outside the alphabet
+pub fn outside_alphabet(words: &Words, config: &Config) -> Vec<String> {
+    words
+        .iter()
+        .filter(|w| {
+            w.iter()
+                .any(|c| *c != SEPARATOR && !config.alphabet.contains(c))
+        })
+        .map(word_str)
+        .collect()
+}
+
+pub fn letter_index(c: char) -> Option<usize> {
+    ASCII_LOWER.iter().position(|l| *l == c)
+}
+
+// a game in progress: the guesses played so far and what they left
+#[derive(Clone, Debug)]
+pub struct GameState {
+    pub words: Words,
+    pub facts: Facts,
+    pub candidates: Words,
+    // letter counts of each word, so filtering needn't recount them every turn
+    counts: Vec<[u8; NUM_CHARS]>,
+    // where the candidates are in words
+    ids: Vec<usize>,
+    pub turns: Vec<(Word, Facts)>,
+    // ids before each turn, for undo
+    history: Vec<Vec<usize>>,
+    cache: FilterCache,
+    // how the entered feedback colors repeated letters
+    mode: DuplicateMode,
+}
+
+impl GameState {
+    pub fn new(words: &Words) -> GameState {
+        GameState {
+            words: words.clone(),
+            facts: Vec::new(),
+            candidates: words.clone(),
+            counts: count_letters(words),
+            ids: (0..words.len()).collect(),
+            turns: Vec::new(),
+            history: Vec::new(),
+            cache: FilterCache::default(),
+            mode: DuplicateMode::default(),
+        }
+    }
+
+    pub fn duplicates(mut self, mode: DuplicateMode) -> GameState {
+        self.mode = mode;
+        self
+    }
+
+    // false when the feedback ruled out none of the candidates
+    pub fn apply(&mut self, guess: Word, feedback: Facts) -> bool {
+        self.facts.extend(feedback.iter().cloned());
+        let ids = self.cache.get(&self.facts).unwrap_or_else(|| {
+            // facts only ever narrow, so the last survivors are all that need scanning
+            let ids: Vec<usize> = self
+                .ids
+                .iter()
+                .copied()
+                .filter(|&i| {
+                    matches_counted(&self.words[i], &self.counts[i], &self.facts, self.mode)
+                })
+                .collect();
+            self.cache.insert(&self.facts, ids.clone());
+            ids
+        });
+        let narrowed = ids.len() < self.ids.len();
+        self.history.push(mem::replace(&mut self.ids, ids));
+        if narrowed {
+            self.candidates = self.ids.iter().map(|&i| self.words[i]).collect();
+        }
+        self.turns.push((guess, feedback));
+        narrowed
+    }
+
+    // bits still needed to single out the answer
+    pub fn remaining_entropy(&self) -> f64 {
+        remaining_entropy(self.candidates.len())
+    }
+
+    pub fn transcript(&self) -> String {
+        let turns: Vec<(Word, String)> = self
+            .turns
+            .iter()
+            .map(|(g, f)| (*g, feedback_colors(f)))
+            .collect();
+        transcript(&turns)
+    }
+
+    // takes back the last turn, false when there is none
+    pub fn undo(&mut self) -> bool {
+        match (self.turns.pop(), self.history.pop()) {
+            (Some((_, feedback)), Some(ids)) => {
+                self.facts.truncate(self.facts.len() - feedback.len());
+                self.candidates = ids.iter().map(|&i| self.words[i]).collect();
+                self.ids = ids;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // best feedback seen for each letter, as shown on the keyboard
+    pub fn keyboard(&self) -> [Option<Feedback>; NUM_CHARS] {
+        let rank = |f: &Option<Feedback>| match f {
+            Some(Feedback::Correct) => 3,
+            Some(Feedback::Used) => 2,
+            Some(Feedback::NotUsed) => 1,
+            _ => 0,
+        };
+        let mut keys = [None; NUM_CHARS];
+        for f in &self.facts {
+            if let Some(i) = letter_index(f.letter) {
+                if rank(&Some(f.feedback)) > rank(&keys[i]) {
+                    keys[i] = Some(f.feedback);
+                }
+            }
+        }
+        keys
+    }
+}
+
+// the pattern a turn's feedback shows, None unless it's WORD_LENGTH known colors
+pub fn facts_to_pattern(feedback: &[Fact]) -> Option<Pattern> {
+    if feedback.len() != WORD_LENGTH {
+        return None;
+    }
+    feedback.iter().rev().try_fold(0, |p, f| {
+        let digit = match f.feedback {
+            Feedback::NotUsed => 0,
+            Feedback::Used => 1,
+            Feedback::Correct => 2,
+            Feedback::Unknown => return None,
+        };
+        Some(p * 3 + digit)
+    })
+}
+
+// a turn's colors as GYB, ? where unknown
+pub fn feedback_colors(feedback: &[Fact]) -> String {
+    feedback
+        .iter()
+        .map(|f| match f.feedback {
+            Feedback::Correct => 'G',
+            Feedback::Used => 'Y',
+            Feedback::NotUsed => 'B',
+            Feedback::Unknown => '?',
+        })
+        .collect()
+}
+
+// the rows of a shared result's emoji grid, other lines such as the "Wordle 1,234 4/6"
+// header skipped; high contrast orange and blue squares read as green and yellow
+pub fn parse_emoji_grid(s: &str) -> Vec<[Feedback; WORD_LENGTH]> {
+    s.lines()
+        .filter_map(|line| {
+            let tiles: Option<Vec<Feedback>> = line
+                .trim()
+                .chars()
+                .filter(|c| *c != '\u{fe0f}')
+                .map(|c| match c {
+                    '\u{1f7e9}' | '\u{1f7e7}' => Some(Feedback::Correct),
+                    '\u{1f7e8}' | '\u{1f7e6}' => Some(Feedback::Used),
+                    '\u{2b1b}' | '\u{2b1c}' => Some(Feedback::NotUsed),
+                    _ => None,
+                })
+                .collect();
+            tiles?.try_into().ok()
+        })
+        .collect()
+}
+
+// one numbered line per turn, e.g. "1. crane BYBGB"
+pub fn transcript(turns: &[(Word, String)]) -> String {
+    turns
+        .iter()
+        .enumerate()
+        .map(|(i, (g, colors))| format!("{}. {} {}\n", i + 1, word_str(g), colors))
+        .collect()
+}
+
+// the game a transcript records, blank lines skipped and the numbers optional
+pub fn parse_transcript(
+    words: &Words,
+    text: &str,
+    mode: DuplicateMode,
+) -> Result<GameState, String> {
+    let mut state = GameState::new(words).duplicates(mode);
+    for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let turn = match line.split_once(". ") {
+            Some((n, rest)) if n.chars().all(|c| c.is_ascii_digit()) => rest,
+            _ => line,
+        };
+        let (guess, feedback) = parse_turn(turn)?;
+        state.apply(guess, feedback);
+    }
+    Ok(state)
+}
+
+// a line of interactive input: the guess played and its colors, e.g. "crane bybgb"
+pub fn parse_turn(line: &str) -> Result<(Word, Facts), String> {
+    let mut parts = line.split_whitespace();
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(guess), Some(colors), None) => {
+            let facts = parse_feedback(guess, colors)?;
+            Ok((to_array(&guess.to_lowercase()), facts))
+        }
+        _ => Err(format!("expected <guess> <colors>, got {:?}", line.trim())),
+    }
+}
+
+// full screen board and keyboard for --tui, the tiles colored with ANSI escapes
+#[cfg(feature = "tui")]
+pub mod tui {
+    use super::*;
+    use crossterm::cursor::{Hide, MoveTo, Show};
+    use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+    use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+    use crossterm::{execute, queue};
+    use std::io::Write;
+
+    const KEY_ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+    pub fn tile(c: char, f: Option<Feedback>) -> String {
+        let color = match f {
+            Some(Feedback::Correct) => "42;30",
+            Some(Feedback::Used) => "43;30",
+            Some(Feedback::NotUsed) => "100;37",
+            _ => "47;30",
+        };
+        format!("\x1b[{}m {} \x1b[0m", color, c.to_ascii_uppercase())
+    }
+
+    pub fn render(state: &GameState, suggestion: Option<&Word>) -> String {
+        let mut out = String::new();
+        for (guess, feedback) in &state.turns {
+            for (c, f) in guess.iter().zip(feedback) {
+                out.push_str(&tile(*c, Some(f.feedback)));
+            }
+            out.push('\n');
+        }
+        out.push('\n');
+        let keys = state.keyboard();
+        for row in KEY_ROWS.iter() {
+            for c in row.chars() {
+                out.push_str(&tile(c, letter_index(c).and_then(|i| keys[i])));
+            }
+            out.push('\n');
+        }
+        out.push('\n');
+        out.push_str(&format!("{} candidates\n", state.candidates.len()));
+        if let Some(w) = suggestion {
+            out.push_str(&format!("Suggestion: {}\n", word_str(w)));
+        }
+        out
+    }
+
+    // what a key press does to the line being typed
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum Edit {
+        Typing,
+        Submit(String),
+        Quit,
+    }
+
+    pub fn edit(input: &mut String, key: KeyEvent) -> Edit {
+        match key.code {
+            KeyCode::Esc => Edit::Quit,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Edit::Quit,
+            KeyCode::Enter => Edit::Submit(mem::take(input)),
+            KeyCode::Backspace => {
+                input.pop();
+                Edit::Typing
+            }
+            KeyCode::Char(c) if c.is_ascii_alphabetic() || c == ' ' || c == '?' => {
+                input.push(c);
+                Edit::Typing
+            }
+            _ => Edit::Typing,
+        }
+    }
+
+    // plays a submitted line the way the line mode does, returning what to tell the player
+    pub fn submit(state: &mut GameState, line: &str) -> String {
+        if line.trim() == "undo" {
+            return match state.undo() {
+                true => String::new(),
+                false => "Nothing to undo".to_string(),
+            };
+        }
+        let (guess, feedback) = match parse_turn(line) {
+            Ok(turn) => turn,
+            Err(e) => return e,
+        };
+        let solved = is_solved(&feedback);
+        let narrowed = state.apply(guess, feedback);
+        if solved {
+            format!("Solved in {}, Esc to quit", state.turns.len())
+        } else if let Err(e) = validate_facts(&state.facts) {
+            format!("{}, enter undo to take back the last turn", e)
+        } else if state.candidates.is_empty() {
+            "No words match these clues, enter undo to take back the last turn".to_string()
+        } else if !narrowed {
+            "That guess gave no new information".to_string()
+        } else {
+            String::new()
+        }
+    }
+
+    // raw mode on the alternate screen, put back however run returns
+    struct Screen;
+
+    impl Screen {
+        fn enter() -> io::Result<Screen> {
+            terminal::enable_raw_mode()?;
+            execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+            Ok(Screen)
+        }
+    }
+
+    impl Drop for Screen {
+        fn drop(&mut self) {
+            let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+            let _ = terminal::disable_raw_mode();
+        }
+    }
+
+    fn draw(
+        out: &mut impl Write,
+        state: &GameState,
+        suggestion: Option<&Word>,
+        message: &str,
+        input: &str,
+    ) -> io::Result<()> {
+        queue!(out, Clear(ClearType::All), MoveTo(0, 0))?;
+        let screen = format!("{}{}\n> {}", render(state, suggestion), message, input);
+        // raw mode doesn't return the cursor to the start of the line
+        write!(out, "{}", screen.replace('\n', "\r\n"))?;
+        out.flush()
+    }
+
+    // the interactive game on a full screen board: "<guess> <colors>" and Enter plays a
+    // turn, "undo" takes one back and Esc quits; a resize redraws like any other event
+    pub fn run(words: &Words, strategy: Strategy, mode: DuplicateMode) -> io::Result<()> {
+        let _screen = Screen::enter()?;
+        let mut out = io::stdout();
+        let mut state = GameState::new(words).duplicates(mode);
+        let mut input = String::new();
+        let mut message = String::new();
+        let suggest = |c: &Words| select_guess(words, c, strategy);
+        let mut suggestion = suggest(&state.candidates);
+        loop {
+            draw(&mut out, &state, suggestion.as_ref(), &message, &input)?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match edit(&mut input, key) {
+                    Edit::Quit => return Ok(()),
+                    Edit::Submit(line) => {
+                        message = submit(&mut state, &line);
+                        suggestion = suggest(&state.candidates);
+                    }
+                    Edit::Typing => {}
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn press(input: &mut String, code: KeyCode) -> Edit {
+            edit(input, KeyEvent::new(code, KeyModifiers::NONE))
+        }
+
+        #[test]
+        fn keys_edit_the_line_until_enter_submits_it() {
+            let mut input = String::new();
+            for c in "crane bbygg!".chars() {
+                assert_eq!(press(&mut input, KeyCode::Char(c)), Edit::Typing);
+            }
+            assert_eq!(input, "crane bbygg");
+            press(&mut input, KeyCode::Backspace);
+            press(&mut input, KeyCode::Char('b'));
+            assert_eq!(
+                press(&mut input, KeyCode::Enter),
+                Edit::Submit("crane bbygb".to_string())
+            );
+            assert!(input.is_empty());
+            assert_eq!(press(&mut input, KeyCode::Esc), Edit::Quit);
+            let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+            assert_eq!(edit(&mut input, ctrl_c), Edit::Quit);
+        }
+
+        #[test]
+        fn submitted_turns_color_the_keyboard_and_undo_takes_them_back() {
+            let words = parse_words(DEFAULT_WORDS);
+            let mut state = GameState::new(&words);
+            assert_eq!(
+                submit(&mut state, "crane"),
+                parse_turn("crane").unwrap_err()
+            );
+            assert_eq!(submit(&mut state, "crane bybgb"), "");
+            let keys = state.keyboard();
+            let key = |c| keys[letter_index(c).unwrap()];
+            assert_eq!(key('c'), Some(Feedback::NotUsed));
+            assert_eq!(key('r'), Some(Feedback::Used));
+            assert_eq!(key('n'), Some(Feedback::Correct));
+            assert_eq!(key('z'), None);
+            assert!(state.candidates.len() < words.len());
+            let board = render(&state, None);
+            assert!(board.starts_with(&tile('c', Some(Feedback::NotUsed))));
+            assert!(board.contains(&format!("{} candidates", state.candidates.len())));
+
+            assert_eq!(submit(&mut state, "undo"), "");
+            assert_eq!(state.candidates.len(), words.len());
+            assert_eq!(submit(&mut state, "undo"), "Nothing to undo");
+            assert_eq!(
+                submit(&mut state, "crane ggggg"),
+                "Solved in 1, Esc to quit"
+            );
+        }
+    }
+}
+
+// entry point for a browser helper, no files or threads needed; the state is
+// {"turns": [{"guess": "raise", "feedback": "BYBBB"}], "strategy": "entropy"}
+// with strategy optional, the reply {"guess": "clout"} or {"error": "..."}
+#[cfg(feature = "wasm")]
+pub mod wasm {
+    use super::*;
+
+    // the string literals of a JSON document in order, which is all the state needs
+    pub fn strings(json: &str) -> Result<Vec<String>, String> {
+        let mut out = Vec::new();
+        let mut chars = json.chars();
+        while let Some(c) = chars.next() {
+            if c != '"' {
+                continue;
+            }
+            let mut s = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => s.extend(chars.next()),
+                    Some(c) => s.push(c),
+                    None => return Err("unterminated string".to_string()),
+                }
+            }
+            out.push(s);
+        }
+        Ok(out)
+    }
+
+    pub fn state(json: &str) -> Result<(Facts, Strategy), String> {
+        let strings = strings(json)?;
+        let mut facts = Facts::new();
+        let mut strategy = Strategy::default();
+        let mut guess = None;
+        let mut pairs = strings.iter();
+        while let Some(key) = pairs.next() {
+            match key.as_str() {
+                "guess" => guess = pairs.next(),
+                "feedback" => {
+                    let colors = pairs.next().ok_or("feedback without colors")?;
+                    let g = guess.take().ok_or("feedback before its guess")?;
+                    facts.append(&mut parse_feedback(g, colors)?);
+                }
+                "strategy" => strategy = pairs.next().ok_or("strategy without a name")?.parse()?,
+                _ => {}
+            }
+        }
+        Ok((facts, strategy))
+    }
+
+    // a JSON string literal, quotes included
+    fn json_string(s: &str) -> String {
+        let mut out = String::from("\"");
+        for c in s.chars() {
+            match c {
+                '"' => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+                c => out.push(c),
+            }
+        }
+        out.push('"');
+        out
+    }
+
+    pub fn suggest(state_json: &str) -> String {
+        let words = parse_words(DEFAULT_WORDS);
+        let reply = state(state_json).and_then(|(facts, strategy)| {
+            let candidates = filter_words(&words, &facts, DuplicateMode::Standard);
+            select_guess(&words, &candidates, strategy).ok_or("no words match".to_string())
+        });
+        match reply {
+            Ok(g) => format!("{{\"guess\": \"{}\"}}", word_str(&g)),
+            Err(e) => format!("{{\"error\": {}}}", json_string(&e)),
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn suggest_replies_with_a_listed_word() {
+            let words = parse_words(DEFAULT_WORDS);
+            let reply = suggest(r#"{"turns": [{"guess": "raise", "feedback": "BYBBB"}]}"#);
+            let guess = reply
+                .strip_prefix("{\"guess\": \"")
+                .and_then(|r| r.strip_suffix("\"}"))
+                .unwrap();
+            assert!(words.contains(&to_array(guess)));
+        }
+
+        #[test]
+        fn suggest_escapes_errors_as_json() {
+            let reply = suggest(r#"{"turns": [{"guess": "raise", "feedback": "BBXBB"}]}"#);
+            assert_eq!(reply, r#"{"error": "unknown color 'X' in \"BBXBB\""}"#);
+        }
+    }
+}
+
+// a practice guess's colors and, when the strategy would have played something else
+// from the same candidates, that guess
+pub fn grade(
+    words: &Words,
+    candidates: &Words,
+    answer: &Word,
+    guess: &Word,
+    strategy: Strategy,
+) -> (String, Option<Word>) {
+    let colors = feedback_colors(&check(answer, guess, DuplicateMode::Standard));
+    let optimal = select_guess(words, candidates, strategy).filter(|g| g != guess);
+    (colors, optimal)
+}
+
+// bit i set when the word contains the i'th letter
+pub fn letter_mask(w: &Word) -> u32 {
+    w.iter()
+        .filter_map(|&c| letter_index(c))
+        .fold(0, |m, i| m | 1 << i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the first n answers of the built in list
+    fn answers(n: usize) -> Words {
+        parse_words(DEFAULT_WORDS).into_iter().take(n).collect()
+    }
+
+    #[test]
+    fn greedy_sample_of_the_whole_list_is_exact() {
+        let words = answers(60);
+        let all = sample_words(&words, words.len(), 7);
+        for g in words.iter().take(5) {
+            assert_eq!(
+                greedy_score(&words, g, &all),
+                greedy_score(&words, g, &words)
+            );
+        }
+        let full = greedy(&words, 5, None, 0);
+        assert_eq!(full, greedy(&words, 5, Some(words.len()), 7));
+        assert_eq!(full[0], (words[0], greedy_score(&words, &words[0], &words)));
+    }
+
+    #[test]
+    fn flexibility_guess_plays_a_listed_word() {
+        let words = answers(40);
+        let candidates: Words = words.iter().step_by(3).copied().collect();
+        let gr = flexibility_guess(&words, &candidates);
+        assert!(words.contains(&gr.guess));
+        assert_eq!(gr.num_candidates, candidates.len());
+        assert_eq!(gr.depths.len(), candidates.len());
+    }
+
+    #[test]
+    fn sampling_the_whole_pool_picks_the_exhaustive_guess() {
+        let words = answers(60);
+        let candidates: Words = words.iter().step_by(2).copied().collect();
+        for seed in 0..3 {
+            assert_eq!(
+                select_guess_sampled(
+                    &words,
+                    &candidates,
+                    Strategy::Entropy,
+                    (words.len(), seed),
+                    1
+                ),
+                select_guess(&words, &candidates, Strategy::Entropy)
+            );
+        }
+    }
+
+    #[test]
+    fn a_cap_of_every_candidate_is_the_exhaustive_search() {
+        let words = answers(12);
+        let all = Some(words.len());
+        let exhaustive = best_guess(&words, &Vec::new()).unwrap();
+        let capped = best_guess_capped(&words, &Vec::new(), all).unwrap();
+        assert_eq!(capped.guess, exhaustive.guess);
+        assert_eq!(capped.guesses, exhaustive.guesses);
+        assert_eq!(capped.depths, exhaustive.depths);
+        let shared = best_guess_shared(&words, &Vec::new(), all);
+        assert_eq!(
+            shared.guesses,
+            best_guess_shared(&words, &Vec::new(), None).guesses
+        );
+        assert_eq!(shared.guesses, exhaustive.guesses);
+    }
+
+    #[test]
+    fn reordered_turns_hit_the_same_filter_cache_entry() {
+        let words = answers(40);
+        let (answer, g, h) = (words[5], words[11], words[23]);
+        let mut first = GameState::new(&words);
+        first.apply(g, check(&answer, &g, DuplicateMode::Standard));
+        first.apply(h, check(&answer, &h, DuplicateMode::Standard));
+        let mut second = GameState::new(&words);
+        second.cache = mem::take(&mut first.cache);
+        second.apply(h, check(&answer, &h, DuplicateMode::Standard));
+        assert_eq!(second.cache.hits, 0);
+        second.apply(g, check(&answer, &g, DuplicateMode::Standard));
+        assert_eq!(second.cache.hits, 1);
+        assert_eq!(second.candidates, first.candidates);
+    }
+
+    #[test]
+    fn incremental_filtering_matches_filtering_from_scratch() {
+        let words = answers(60);
+        for i in 0..20 {
+            let answer = words[i * 7 % words.len()];
+            let mut state = GameState::new(&words);
+            for g in [i * 13, i * 29, i * 31] {
+                let g = words[g % words.len()];
+                state.apply(g, check(&answer, &g, DuplicateMode::Standard));
+                assert_eq!(
+                    state.candidates,
+                    filter_words(&words, &state.facts, DuplicateMode::Standard)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn a_spent_budget_still_returns_a_guess_quickly() {
+        let words = answers(300);
+        let start = Instant::now();
+        let gr = best_guess_timed(&words, &Vec::new(), Strategy::Entropy, Duration::ZERO, None);
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(words.contains(&gr.guess));
+        assert_eq!(gr.depths.len(), words.len());
+    }
+
+    #[test]
+    fn a_spent_budget_plays_the_strategys_first_choice() {
+        let words = answers(300);
+        let first = |strategy| {
+            words
+                .iter()
+                .min_by(|a, b| {
+                    let cost = |g| evaluate_guess(&words, g).cost(strategy);
+                    cost(a).partial_cmp(&cost(b)).unwrap()
+                })
+                .copied()
+                .unwrap()
+        };
+        let played =
+            |strategy| best_guess_timed(&words, &Vec::new(), strategy, Duration::ZERO, None).guess;
+        assert_ne!(first(Strategy::Entropy), first(Strategy::Minimax));
+        for strategy in [Strategy::Entropy, Strategy::Minimax] {
+            assert_eq!(played(strategy), first(strategy));
+        }
+    }
+
+    #[test]
+    fn an_exact_count_keeps_only_words_with_that_many() {
+        let words = parse_words("crane\nelder\ngeese\nsheep\nberet\nlever\n");
+        let two_es = parse_exact("e:2").unwrap();
+        let kept: Vec<String> = words
+            .iter()
+            .filter(|w| two_es.matches(w))
+            .map(word_str)
+            .collect();
+        assert_eq!(kept, ["elder", "sheep", "beret", "lever"]);
+        assert!(parse_exact("e:6").is_err());
+        assert!(parse_exact("ee:2").is_err());
+    }
+
+    #[test]
+    fn tree_dot_is_a_digraph_with_edges_and_leaves_labeled_by_pattern() {
+        let words = answers(40);
+        let opener = words[0];
+        let root = expand_decision_tree(&words, &words, &opener, Strategy::Entropy, 3);
+        let dot = tree_dot(&root);
+        assert!(dot.starts_with("digraph {\n"));
+        assert!(dot.ends_with("}\n"));
+        let body: Vec<&str> = dot.lines().collect();
+        assert!(body[1..body.len() - 1].iter().all(|l| l.ends_with("];")));
+
+        let solved = (NUM_PATTERNS - 1) as Pattern;
+        let groups = partition_by_pattern(&words, &opener);
+        let (shared, _) = groups.iter().find(|(_, g)| g.len() > 1).unwrap();
+        let label = format!("[label=\"{}\"];", pattern_string(*shared));
+        assert!(body
+            .iter()
+            .any(|l| l.starts_with("  n0 -> ") && l.ends_with(&label)));
+        let (single, g) = groups
+            .iter()
+            .find(|(p, g)| g.len() == 1 && *p != solved)
+            .unwrap();
+        let leaf = format!("\\n{} {}", pattern_string(*single), word_str(&g[0]));
+        assert!(body[1].starts_with("  n0 [label=") && body[1].contains(&leaf));
+    }
+
+    #[test]
+    fn tree_depths_count_the_guesses_each_answer_takes() {
+        let words = answers(40);
+        let opener = words[0];
+        let gr = solve_all(&words, &words, &opener, Strategy::Entropy);
+        assert_eq!(gr.depths.len(), words.len());
+        for (w, d) in gr.depths {
+            assert_eq!(d, simulate(&words, &w, &opener, Strategy::Entropy).len());
+        }
+    }
+
+    #[test]
+    fn a_smaller_alphabet_only_counts_its_own_letters() {
+        let words = answers(40);
+        let config = Config::default().alphabet(parse_alphabet("zoedcba").unwrap());
+        let k = letter_index('k').unwrap();
+        assert_eq!(letter_frequencies(&words, &config)[k], 0);
+        assert!(letter_frequencies(&words, &Config::default())[k] > 0);
+        assert_eq!(unused_letters(&words, &config), ['z']);
+        let outside = outside_alphabet(&words, &config);
+        assert!(outside.contains(&"aback".to_string()));
+        assert!(!outside.contains(&"abode".to_string()));
+        let csv = heatmap_csv(&positional_frequencies(&words), &config);
+        assert_eq!(csv.lines().count(), 1 + config.alphabet.len());
+        // abode and adobe are the only words using five of the letters, abode listed first
+        let pool = frequency_pool(&words, 1, &config);
+        assert_eq!(word_str(&words[pool[0]]), "abode");
+    }
+
+    #[test]
+    fn heatmap_has_a_header_and_a_row_per_letter() {
+        let words = answers(40);
+        let csv = heatmap_csv(&positional_frequencies(&words), &Config::default());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "letter,pos0,pos1,pos2,pos3,pos4");
+        assert_eq!(lines.len(), 1 + NUM_CHARS);
+        // every word starts with a, and aback to abyss have b second
+        assert!(lines[1].starts_with("a,40,"));
+        assert_eq!(lines[2].split(',').nth(2), Some("14"));
+    }
+
+    #[test]
+    fn try_check_is_none_for_the_wrong_length() {
+        let (crane, slate) = (to_array("crane"), to_array("slate"));
+        assert_eq!(
+            try_check(&crane, &slate),
+            Some(check(&crane, &slate, DuplicateMode::Standard))
+        );
+        assert_eq!(try_check(&crane[..4], &slate), None);
+        assert_eq!(try_check(&crane, &['s'; 6]), None);
+    }
+
+    #[test]
+    fn facts_builder_and_defactify_agree_with_factify() {
+        let correct = vec![('l', 1)];
+        let used = vec![('l', 3), ('l', 0)];
+        let facts = factify(&correct, &used, "chaps");
+        let mut builder = FactsBuilder::new().correct('l', 1).correct('l', 1);
+        builder = builder.present('l', 3).present('l', 0);
+        for c in "chapsc".chars() {
+            builder = builder.absent(c);
+        }
+        assert_eq!(builder.build(), facts);
+        assert_eq!(defactify(&facts), (correct, used, "chaps".to_string()));
+    }
+
+    #[test]
+    fn letter_masks_filter_like_contains() {
+        let words = answers(200);
+        for w in &words {
+            for (i, l) in ASCII_LOWER.iter().enumerate() {
+                assert_eq!(letter_mask(w) >> i & 1 == 1, w.contains(l));
+            }
+        }
+        for i in 0..50 {
+            let facts = check(
+                &words[i * 7 % words.len()],
+                &words[i * 13 % words.len()],
+                DuplicateMode::Standard,
+            );
+            let mask = |keep: fn(&Feedback) -> bool| {
+                facts
+                    .iter()
+                    .filter(|f| keep(&f.feedback))
+                    .filter_map(|f| letter_index(f.letter))
+                    .fold(0u32, |m, i| m | 1 << i)
+            };
+            let with = mask(|f| *f != Feedback::NotUsed);
+            let without = mask(|f| *f == Feedback::NotUsed) & !with;
+            let masked: Vec<&Word> = words
+                .iter()
+                .filter(|w| letter_mask(w) & with == with && letter_mask(w) & without == 0)
+                .collect();
+            let scanned: Vec<&Word> = words
+                .iter()
+                .filter(|w| {
+                    (0..NUM_CHARS).all(|i| {
+                        let has = w.contains(&ASCII_LOWER[i]);
+                        (with >> i & 1 == 0 || has) && (without >> i & 1 == 0 || !has)
+                    })
+                })
+                .collect();
+            assert_eq!(masked, scanned);
+        }
+    }
+
+    // a guess letter both shown and gray means the answer has as many copies as were
+    // shown, so every word left has exactly that many and the answer is among them
+    #[test]
+    fn mixed_colors_on_a_letter_filter_as_its_exact_count() {
+        let words = parse_words(DEFAULT_WORDS);
+        let shown = |facts: &Facts, l: char| {
+            facts
+                .iter()
+                .filter(|f| {
+                    f.letter == l && matches!(f.feedback, Feedback::Correct | Feedback::Used)
+                })
+                .count()
+        };
+        let mixed: Vec<(Word, Facts)> = words
+            .iter()
+            .step_by(23)
+            .flat_map(|a| {
+                words
+                    .iter()
+                    .step_by(97)
+                    .map(move |g| (*a, check(a, g, DuplicateMode::Standard)))
+            })
+            .filter(|(_, facts)| {
+                facts
+                    .iter()
+                    .any(|f| f.feedback == Feedback::NotUsed && shown(facts, f.letter) > 0)
+            })
+            .collect();
+        assert!(!mixed.is_empty());
+        for (a, facts) in &mixed {
+            let left = filter_words(&words, facts, DuplicateMode::Standard);
+            assert!(left.contains(a));
+            for w in &left {
+                for f in facts.iter().filter(|f| f.feedback == Feedback::NotUsed) {
+                    let n = w.iter().filter(|c| **c == f.letter).count();
+                    assert_eq!(n, shown(facts, f.letter));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn word_regex_anchors() {
+        let re = |p: &str| WordRegex::new(p).unwrap();
+        assert!(re("ab").is_match("cabin"));
+        assert!(!re("^ab").is_match("cabin"));
+        assert!(re("^ab").is_match("abbey"));
+        assert!(re("ey$").is_match("abbey"));
+        assert!(!re("ab$").is_match("abbey"));
+        assert!(re("^abbey$").is_match("abbey"));
+        assert!(!re("^abbe$").is_match("abbey"));
+    }
+
+    #[test]
+    fn word_regex_classes() {
+        let re = |p: &str| WordRegex::new(p).unwrap();
+        assert!(re("^[aeiou]").is_match("adobe"));
+        assert!(!re("^[aeiou]").is_match("crane"));
+        assert!(re("^[^aeiou]").is_match("crane"));
+        assert!(re("^c[p-s]").is_match("crane"));
+        assert!(!re("^c[a-f]").is_match("crane"));
+        assert!(re("^.r.n.$").is_match("crane"));
+        assert!(WordRegex::new("[ab").is_err());
+        assert!(WordRegex::new("[]").is_err());
+        assert!(WordRegex::new("[a-]").is_err());
+    }
+
+    #[test]
+    fn word_regex_repeats() {
+        let re = |p: &str| WordRegex::new(p).unwrap();
+        assert!(re("^ab+ey$").is_match("abbey"));
+        assert!(!re("^ab+ey$").is_match("aey"));
+        assert!(re("^ab*ey$").is_match("aey"));
+        assert!(re("^ab?ey$").is_match("abey"));
+        assert!(!re("^ab?ey$").is_match("abbey"));
+        assert!(re("^a.*t$").is_match("adapt"));
+        assert!(re("^[^e]*e[^e]*$").is_match("crane"));
+        assert!(!re("^[^e]*e[^e]*$").is_match("geese"));
+        assert!(WordRegex::new("*a").is_err());
+        assert!(WordRegex::new("a|b").is_err());
+    }
+
+    #[test]
+    fn a_simple_pattern_reduces_the_list() {
+        let words = answers(40);
+        let re = WordRegex::new("^ab").unwrap();
+        let kept: Words = words
+            .iter()
+            .filter(|w| re.is_match(&word_str(w)))
+            .copied()
+            .collect();
+        assert_eq!(kept.len(), 14);
+        assert_eq!(word_str(&kept[0]), "aback");
+        assert_eq!(word_str(&kept[13]), "abyss");
+    }
+
+    #[test]
+    fn streaming_and_matrix_tables_rank_the_same() {
+        let words = answers(300);
+        let candidates = candidate_indices(
+            &words,
+            &check(&words[17], &words[200], DuplicateMode::Standard),
+            DuplicateMode::Standard,
+        );
+        let all: Vec<usize> = (0..words.len()).collect();
+        let rank = |streaming| {
+            let table = PatternTable::new(&words, streaming);
+            for &g in all.iter().step_by(37) {
+                for &a in &candidates {
+                    assert_eq!(table.get(g, a), pattern(&words[a], &words[g]));
+                }
+            }
+            rank_guesses(&table, &all, &candidates, Strategy::Entropy)
+                .into_iter()
+                .map(|(w, _)| w)
+                .collect::<Words>()
+        };
+        assert_eq!(rank(true), rank(false));
+    }
+
+    #[test]
+    fn anagrams_collapse_to_one_entry() {
+        let candidates = answers(10);
+        let guesses = parse_words("raise\ncrane\narise\n");
+        let ranked = rank_openers(&candidates, &guesses, Strategy::Entropy);
+        let kept = collapse_equivalent(&ranked, &candidates, 3, Strategy::Entropy, false);
+        let kept: Vec<String> = kept.iter().map(|(w, _)| word_str(w)).collect();
+        assert_eq!(kept.len(), 2);
+        assert!(kept.contains(&"crane".to_string()));
+        // of the two the alphabetically first stands for both
+        assert!(kept.contains(&"arise".to_string()));
+    }
+
+    #[test]
+    fn the_distribution_counts_every_candidate_once() {
+        let words = answers(12);
+        let gr = best_guess(&words, &Vec::new()).unwrap();
+        let dist = gr.distribution();
+        assert_eq!(dist.iter().sum::<usize>(), words.len());
+        assert_eq!(dist[0], 1);
+    }
+
+    #[test]
+    fn lists_with_crlf_endings_and_a_bom_load() {
+        let path = env::temp_dir().join("wordle-test-crlf.txt");
+        let path = path.to_string_lossy();
+        fs::write(&*path, "\u{feff}crane\r\n slate \r\nabbey\r\n").unwrap();
+        let words = load_words(&path).unwrap();
+        fs::remove_file(&*path).unwrap();
+        let names: Vec<String> = words.iter().map(word_str).collect();
+        assert_eq!(names, ["crane", "slate", "abbey"]);
+    }
+
+    #[test]
+    fn word_str_spells_the_guess() {
+        let gr = GuessResult {
+            guess: to_array("crane"),
+            guesses: 3,
+            num_candidates: 2,
+            depths: vec![(to_array("crane"), 1), (to_array("crate"), 2)],
+            partition_sizes: vec![1, 1],
+        };
+        assert_eq!(gr.word_str(), "crane");
+        assert_eq!(word_str(&gr.depths[1].0), "crate");
+    }
+
+    #[test]
+    fn a_guess_pool_of_every_word_ranks_like_no_pool() {
+        let words = answers(100);
+        let pool = frequency_pool(&words, words.len(), &Config::default());
+        let all: Vec<usize> = (0..words.len()).collect();
+        assert_eq!(pool, all);
+        let table = PatternTable::new(&words, false);
+        let candidates = candidate_indices(
+            &words,
+            &check(&words[3], &words[50], DuplicateMode::Standard),
+            DuplicateMode::Standard,
+        );
+        let names = |ranked: Vec<(Word, GuessEval)>| -> Words {
+            ranked.into_iter().map(|(w, _)| w).collect()
+        };
+        assert_eq!(
+            names(rank_guesses(&table, &pool, &candidates, Strategy::Entropy)),
+            names(rank_guesses(&table, &all, &candidates, Strategy::Entropy))
+        );
+        assert_eq!(frequency_pool(&words, 10, &Config::default()).len(), 10);
+    }
+
+    #[test]
+    fn factify_ties_only_colored_letters_to_a_position() {
+        let facts = factify(&[('l', 1)], &[('l', 3), ('l', 0)], "chaps");
+        assert_eq!(facts.len(), 8);
+        assert_eq!(
+            facts[0],
+            Fact {
+                letter: 'l',
+                position: Some(1),
+                feedback: Feedback::Correct
+            }
+        );
+        assert_eq!(facts[2].position, Some(0));
+        assert!(facts[3..]
+            .iter()
+            .all(|f| f.position.is_none() && f.feedback == Feedback::NotUsed));
+        let letters: String = facts[3..].iter().map(|f| f.letter).collect();
+        assert_eq!(letters, "chaps");
+    }
+
+    #[test]
+    fn an_absent_letters_position_is_never_used_in_filtering() {
+        let words = answers(200);
+        let absent = |position| {
+            vec![Fact {
+                letter: 's',
+                position,
+                feedback: Feedback::NotUsed,
+            }]
+        };
+        let expected = filter_words(&words, &absent(None), DuplicateMode::Standard);
+        assert!(expected.len() < words.len());
+        assert!(expected.iter().all(|w| !w.contains(&'s')));
+        for mode in [DuplicateMode::Standard, DuplicateMode::Lenient] {
+            for p in 0..WORD_LENGTH {
+                assert_eq!(filter_words(&words, &absent(Some(p)), mode), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn solve_shared_matches_solve() {
+        let words = answers(12);
+        let old = solve(&words, &words);
+        let shared = solve_shared(&words, &words);
+        assert_eq!(old.len(), shared.len());
+        for (a, b) in old.iter().zip(&shared) {
+            assert_eq!(a.guess, b.guess);
+            assert_eq!(a.guesses, b.guesses);
+            assert_eq!(a.depths, b.depths);
+        }
+    }
+
+    #[test]
+    fn the_explanation_gives_the_candidate_and_group_counts() {
+        let candidates = answers(10);
+        let guess = to_array("raise");
+        let groups = evaluate_guess(&candidates, &guess).groups;
+        let text = explain_guess(&candidates, &guess, DEFAULT_PRECISION);
+        assert!(text.starts_with("raise narrows 10 candidates into "));
+        assert!(text.contains(&format!("into {} groups", groups)));
+    }
+
+    #[test]
+    fn an_unknown_tile_imposes_no_filtering() {
+        let words = answers(200);
+        let known = parse_feedback("crane", "bbbbb").unwrap();
+        let unsure = parse_feedback("crane", "bb?bb").unwrap();
+        assert_eq!(unsure[2].feedback, Feedback::Unknown);
+        let without_a: Facts = known.iter().filter(|f| f.letter != 'a').cloned().collect();
+        assert_eq!(
+            filter_words(&words, &unsure, DuplicateMode::Standard),
+            filter_words(&words, &without_a, DuplicateMode::Standard)
+        );
+        let all_unknown = parse_feedback("crane", "?????").unwrap();
+        assert_eq!(
+            filter_words(&words, &all_unknown, DuplicateMode::Standard),
+            words
+        );
+    }
+
+    #[test]
+    fn guaranteed_two_finds_a_full_split_only_when_there_is_one() {
+        let words = answers(40);
+        let candidates = parse_words("aback\nabase\nabate\n");
+        let g = find_guaranteed_two(&words, &candidates).unwrap();
+        assert_eq!(evaluate_guess(&candidates, &g).worst, 1);
+        let ights = parse_words("fight\nlight\nmight\nnight\nright\nsight\ntight\n");
+        assert_eq!(find_guaranteed_two(&ights, &ights), None);
+    }
+
+    #[test]
+    fn solve_results_sort_by_guesses_then_word() {
+        let words = answers(10);
+        let mut res = solve_shared(&words, &words);
+        res.reverse();
+        sort_results(&mut res, None);
+        let keys: Vec<(usize, Word)> = res.iter().map(|gr| (gr.guesses, gr.guess)).collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_eq!(keys, sorted);
+    }
+
+    #[test]
+    fn the_prefilter_keeps_one_guess_per_grouping_and_the_same_choice() {
+        let words = answers(12);
+        let twins = vec![words[2], words[2]];
+        assert!(branch_guesses(&twins, None).is_empty());
+        assert_eq!(best_guess(&twins, &Vec::new()).unwrap().guess, words[2]);
+        // either word of a pair splits it into the same two singletons
+        let pair = vec![words[0], words[1]];
+        assert_eq!(branch_guesses(&pair, None), vec![words[0]]);
+
+        let kept = branch_guesses(&words, None);
+        let groupings: Vec<Vec<u8>> = kept
+            .iter()
+            .map(|g| partition_signature(&words, g))
+            .collect();
+        assert!(kept.len() < words.len());
+        assert!(words
+            .iter()
+            .all(|g| groupings.contains(&partition_signature(&words, g))));
+        let exhaustive = best_guess(&words, &Vec::new()).unwrap();
+        let shared = best_guess_shared(&words, &Vec::new(), None);
+        assert_eq!(
+            (exhaustive.guess, exhaustive.guesses),
+            (shared.guess, shared.guesses)
+        );
+    }
+
+    #[test]
+    fn extra_copies_of_a_letter_are_gray_only_in_standard_mode() {
+        use Feedback::*;
+        let (answer, guess) = (to_array("crane"), to_array("eerie"));
+        assert_eq!(
+            check_array(&answer, &guess, DuplicateMode::Standard),
+            [NotUsed, NotUsed, Used, NotUsed, Correct]
+        );
+        assert_eq!(
+            check_array(&answer, &guess, DuplicateMode::Lenient),
+            [Used, Used, Used, NotUsed, Correct]
+        );
+        let mut words = answers(100);
+        words.push(answer);
+        for mode in [DuplicateMode::Standard, DuplicateMode::Lenient] {
+            let feedback = check(&answer, &guess, mode);
+            let left = filter_words(&words, &feedback, mode);
+            assert!(left.contains(&answer));
+            let mut state = GameState::new(&words).duplicates(mode);
+            state.apply(guess, feedback);
+            assert_eq!(state.candidates, left);
+        }
+    }
+
+    #[test]
+    fn precomputed_letter_counts_match_counting_directly() {
+        let words = answers(300);
+        let counts = count_letters(&words);
+        for (w, c) in words.iter().zip(&counts).step_by(7) {
+            for (l, n) in ('a'..='z').zip(c.iter()) {
+                assert_eq!(*n as usize, w.iter().filter(|&&x| x == l).count());
+            }
+        }
+        let facts = check_str("abbey", "babes");
+        assert_eq!(
+            filter_words_counted(&words, &counts, &facts, DuplicateMode::Standard),
+            filter_words(&words, &facts, DuplicateMode::Standard)
+        );
+    }
+
+    #[test]
+    fn tree_stats_branches_are_the_openers_distinct_patterns() {
+        let words = answers(80);
+        for opener in [words[0], words[41], to_array("crane")] {
+            let patterns: HashSet<Pattern> = words.iter().map(|w| pattern(w, &opener)).collect();
+            let stats = tree_stats(&words, &opener, Strategy::Entropy);
+            assert_eq!(stats.branches, patterns.len());
+            assert!(stats.leaves >= stats.branches - words.contains(&opener) as usize);
+        }
+    }
+
+    #[test]
+    fn a_prefix_limits_the_guess_to_words_starting_with_it() {
+        let words = answers(60);
+        for prefix in ["ad", "ag", "abo"] {
+            let gr = best_guess_with_prefix(&words, &words, prefix, Strategy::Entropy).unwrap();
+            assert!(gr.word_str().starts_with(prefix), "{}", gr.word_str());
+        }
+        assert!(best_guess_with_prefix(&words, &words, "zz", Strategy::Entropy).is_none());
+    }
+
+    #[test]
+    fn the_difficulty_ranking_is_sorted_and_has_every_answer_once() {
+        let words = answers(60);
+        let ranked = rank_answers_by_difficulty(&words, &words[10], Strategy::Entropy);
+        assert!(ranked.windows(2).all(|p| p[0].1 <= p[1].1));
+        let mut seen: Words = ranked.iter().map(|(w, _)| *w).collect();
+        seen.sort();
+        let mut expected = words.clone();
+        expected.sort();
+        assert_eq!(seen, expected);
+        assert_eq!(ranked[0], (words[10], 1));
+    }
+
+    #[test]
+    fn a_guess_of_only_tested_letters_has_no_new_letters() {
+        let facts = check_str("crane", "slate");
+        assert_eq!(new_letter_count(&facts, &to_array("least")), 0);
+        assert_eq!(new_letter_count(&facts, &to_array("tales")), 0);
+        assert_eq!(new_letter_count(&facts, &to_array("crane")), 3);
+        assert_eq!(new_letter_count(&Vec::new(), &to_array("geese")), 3);
+    }
+
+    #[test]
+    fn a_fixed_date_maps_to_a_stable_daily_index() {
+        // 2022-01-01, puzzle 196
+        let day = 18_993;
+        assert_eq!(daily_index(day, 2315), (196, 196));
+        assert_eq!(daily_index(day, 100), (196, 96));
+        assert_eq!(daily_index(day + 1, 100), (197, 97));
+        assert_eq!(daily_index(FIRST_WORDLE_DAY, 100), (0, 0));
+    }
+
+    #[test]
+    fn merging_lists_keeps_the_first_copy_of_a_shared_word() {
+        let a = answers(5);
+        let b = vec![to_array("crane"), a[3], to_array("slate")];
+        let merged = merge_words(&[a.clone(), b]);
+        assert_eq!(merged.len(), 7);
+        assert_eq!(&merged[..5], &a[..]);
+        assert_eq!(&merged[5..], &[to_array("crane"), to_array("slate")]);
+    }
+
+    #[test]
+    fn prefer_order_breaks_ties_by_list_position() {
+        // raise is the commoner word, listed before its anagram arise
+        let candidates = answers(10);
+        let guesses = parse_words("raise\ncrane\narise\n");
+        let ranked = rank_openers(&candidates, &guesses, Strategy::Entropy);
+        let kept = collapse_equivalent(&ranked, &candidates, 3, Strategy::Entropy, true);
+        let kept: Vec<String> = kept.iter().map(|(w, _)| word_str(w)).collect();
+        assert!(kept.contains(&"raise".to_string()));
+        assert!(!kept.contains(&"arise".to_string()));
+
+        let tied = |w| GuessResult::from_csv(&format!("{},9,3", w)).unwrap();
+        let mut results = vec![tied("arise"), tied("raise")];
+        sort_results(&mut results, Some(&guesses));
+        assert_eq!(results[0].word_str(), "raise");
+        sort_results(&mut results, None);
+        assert_eq!(results[0].word_str(), "arise");
+    }
+
+    #[test]
+    fn undo_restores_the_candidates_from_before_the_turn() {
+        let words = answers(100);
+        let mut state = GameState::new(&words);
+        assert!(!state.undo());
+        let guess = to_array("slate");
+        state.apply(guess, check_str("about", "slate"));
+        let (candidates, facts) = (state.candidates.clone(), state.facts.clone());
+
+        // a mistyped color leaves nothing
+        let typo = to_array("crane");
+        assert!(state.apply(typo, check_str("crane", "crane")));
+        assert!(state.candidates.is_empty());
+        assert!(state.undo());
+        assert_eq!(state.candidates, candidates);
+        assert_eq!(state.facts, facts);
+        assert_eq!(state.turns.len(), 1);
+
+        assert!(state.undo());
+        assert_eq!(state.candidates, words);
+        assert!(state.facts.is_empty());
+    }
+
+    #[test]
+    fn partition_sizes_sum_to_the_candidate_count() {
+        let words = answers(30);
+        let opener = to_array("crane");
+        let results = [
+            solve_all(&words, &words, &opener, Strategy::Entropy),
+            best_guess(&words, &Vec::new()).unwrap(),
+            solve(&words, &words[..2].to_vec()).remove(0),
+        ];
+        for gr in &results {
+            assert_eq!(gr.partition_sizes.iter().sum::<usize>(), words.len());
+            assert!(gr.partition_sizes.windows(2).all(|p| p[0] <= p[1]));
+        }
+        let gr = GuessResult {
+            partition_sizes: vec![1, 2, 3, 4, 20],
+            ..results[0].clone()
+        };
+        assert_eq!(gr.partition_summary(), "min 1 / median 3 / max 20");
+    }
+
+    #[test]
+    fn the_report_is_a_markdown_table_with_a_row_per_opener() {
+        let words = answers(30);
+        let report = opener_report(&words, Strategy::Entropy, DEFAULT_PRECISION);
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(
+            lines[0],
+            "| Opener | Avg guesses | Worst case | Solved in 6 |"
+        );
+        assert_eq!(lines[1], "|---|---|---|---|");
+        assert_eq!(lines.len(), 2 + REPORT_OPENERS);
+        let best = rank_openers(&words, &words, Strategy::Entropy);
+        for ((opener, _), row) in best.iter().zip(&lines[2..]) {
+            assert!(
+                row.starts_with(&format!("| {} |", word_str(opener))),
+                "{}",
+                row
+            );
+            assert_eq!(row.matches('|').count(), 5);
+        }
+    }
+
+    #[test]
+    fn whitespace_around_a_pasted_guess_is_ignored() {
+        let expected = parse_feedback("crane", "GBBYB").unwrap();
+        assert_eq!(parse_feedback(" crane ", "GBBYB\n").unwrap(), expected);
+        assert_eq!(parse_feedback("\tCRANE\r\n", " gbbyb ").unwrap(), expected);
+        let (guess, facts) = parse_turn("  crane   GBBYB \r\n").unwrap();
+        assert_eq!(guess, to_array("crane"));
+        assert_eq!(facts, expected);
+    }
+
+    #[test]
+    fn a_forced_opener_is_always_played_first() {
+        let words = answers(40);
+        let opener = to_array("salet");
+        for answer in &words {
+            let line = simulate(&words, answer, &opener, Strategy::Entropy);
+            assert_eq!(line[0], opener);
+            assert_eq!(line.last(), Some(answer));
+        }
+        let report = RunReport::run(&words, Strategy::Entropy, Some(opener)).unwrap();
+        assert_eq!(report.opener, opener);
+    }
+
+    #[test]
+    fn remaining_entropy_falls_as_facts_accumulate() {
+        let words = answers(200);
+        let answer = words[150];
+        let mut state = GameState::new(&words);
+        let mut bits = vec![state.remaining_entropy()];
+        for guess in simulate(&words, &answer, &to_array("crane"), Strategy::Entropy) {
+            state.apply(guess, check(&answer, &guess, DuplicateMode::Standard));
+            bits.push(state.remaining_entropy());
+        }
+        assert_eq!(bits[0], (words.len() as f64).log2());
+        assert!(bits.windows(2).all(|p| p[1] <= p[0]), "{:?}", bits);
+        assert_eq!(*bits.last().unwrap(), 0.0);
+    }
+
+    #[test]
+    fn parallel_solve_all_matches_the_tree() {
+        let words = answers(60);
+        for opener in [words[0], to_array("crane")] {
+            let seq = solve_all(&words, &words, &opener, Strategy::Entropy);
+            let par = solve_all_par(&words, &words, &opener, Strategy::Entropy);
+            assert_eq!(par.distribution(), seq.distribution());
+            assert_eq!(par.guesses, seq.guesses);
+            for (w, d) in &seq.depths {
+                assert_eq!(par.depth_of(w), *d);
+            }
+        }
+    }
+
+    #[test]
+    fn compare_guess_reports_both_scores() {
+        let words = answers(30);
+        let mine = to_array("fuzzy");
+        let text = compare_guess(&words, &words, &mine, Strategy::Minimax, DEFAULT_PRECISION);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let chosen = select_guess(&words, &words, Strategy::Minimax).unwrap();
+        let (best, yours) = (
+            evaluate_guess(&words, &chosen),
+            evaluate_guess(&words, &mine),
+        );
+        assert_eq!(
+            lines[0],
+            format!(
+                "Chosen: {} Minimax: {} Worst case: {} left",
+                word_str(&chosen),
+                best.worst,
+                best.worst
+            )
+        );
+        assert_eq!(
+            lines[1],
+            format!(
+                "Yours:  fuzzy Minimax: {} Worst case: {} left",
+                yours.worst, yours.worst
+            )
+        );
+        assert!(best.worst < yours.worst);
+    }
+
+    #[test]
+    fn a_guess_of_the_answer_is_solved() {
+        for w in answers(50) {
+            assert!(is_solved(&check(&w, &w, DuplicateMode::Standard)));
+        }
+        assert!(!is_solved(&check_str("crane", "crate")));
+        assert!(!is_solved(&Vec::new()));
+    }
+
+    #[test]
+    fn the_par_iter_paths_match_plain_iteration() {
+        // holds with and without the parallel feature
+        let words = answers(50);
+        let table = PatternTable::new(&words, false);
+        let ids: Vec<usize> = (0..words.len()).collect();
+        for g in 0..words.len() {
+            for a in 0..words.len() {
+                assert_eq!(table.get(g, a), pattern(&words[a], &words[g]));
+            }
+        }
+
+        let mut expected: Vec<(Word, GuessEval)> = words
+            .iter()
+            .map(|g| (*g, evaluate_guess(&words, g)))
+            .collect();
+        sort_by_cost(&mut expected, Strategy::Entropy);
+        let ranked = rank_guesses(&table, &ids, &ids, Strategy::Entropy);
+        let order = |r: &[(Word, GuessEval)]| r.iter().map(|(w, _)| *w).collect::<Words>();
+        assert_eq!(order(&ranked), order(&expected));
+
+        let gr = solve_all(&words, &words, &words[0], Strategy::Entropy);
+        assert_eq!(par_distribution(&gr.depths), gr.distribution());
+    }
+
+    #[test]
+    fn check_array_matches_check() {
+        let words = answers(60);
+        for answer in words.iter().step_by(3) {
+            for guess in words.iter().step_by(5) {
+                let facts = check(answer, guess, DuplicateMode::Standard);
+                let feedback = check_array(answer, guess, DuplicateMode::Standard);
+                assert_eq!(feedback_facts(guess, &feedback), facts);
+                for (i, f) in facts.iter().enumerate() {
+                    assert_eq!(f.feedback, feedback[i]);
+                    assert_eq!(f.letter, guess[i]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn the_tail_goal_can_pick_a_different_opener_than_the_average() {
+        let words = answers(30);
+        let played: Vec<GuessResult> = words
+            .iter()
+            .map(|o| solve_all(&words, &words, o, Strategy::Entropy))
+            .collect();
+        let avg = best_opener(&words, &words, Strategy::Entropy, OpenerGoal::Average).unwrap();
+        let tail = best_opener(&words, &words, Strategy::Entropy, OpenerGoal::Tail).unwrap();
+        assert!(played.iter().all(|gr| avg.guesses <= gr.guesses));
+        assert!(played.iter().all(|gr| tail_count(&tail) <= tail_count(gr)));
+        assert_ne!(avg.guess, tail.guess);
+    }
+
+    #[test]
+    fn blank_lines_in_a_list_are_skipped() {
+        let path = env::temp_dir().join("wordle-test-blank-lines.txt");
+        let path = path.to_string_lossy();
+        fs::write(&*path, "\ncrane\n\n   \nslate\n\t\nabbey\n\n").unwrap();
+        let words = load_words(&path).unwrap();
+        fs::remove_file(&*path).unwrap();
+        assert_eq!(words.len(), 3);
+        assert_eq!(words[1], to_array("slate"));
+        assert_eq!(parse_words("\n \ncrane\n\n"), vec![to_array("crane")]);
+    }
+
+    #[test]
+    fn pattern_counts_sum_to_the_candidates_with_one_all_green() {
+        let words = answers(80);
+        let counts = pattern_counts(&words, &words[7]);
+        assert_eq!(counts.iter().map(|(_, n)| n).sum::<usize>(), words.len());
+        assert!(counts.windows(2).all(|p| p[0].1 >= p[1].1));
+        assert!(counts.contains(&("GGGGG".to_string(), 1)));
+
+        let outside = pattern_counts(&words, &to_array("fuzzy"));
+        assert_eq!(outside.iter().map(|(_, n)| n).sum::<usize>(), words.len());
+        assert!(outside.iter().all(|(p, _)| p != "GGGGG"));
+    }
+
+    #[test]
+    fn a_transcript_round_trips_through_parsing() {
+        let words = answers(200);
+        let answer = words[120];
+        let turns: Vec<(Word, String)> =
+            simulate(&words, &answer, &to_array("crane"), Strategy::Entropy)
+                .iter()
+                .map(|g| {
+                    (
+                        *g,
+                        feedback_colors(&check(&answer, g, DuplicateMode::Standard)),
+                    )
+                })
+                .collect();
+        let text = transcript(&turns);
+        assert!(text.starts_with("1. crane "));
+        assert!(text.ends_with(&format!("{} GGGGG\n", word_str(&answer))));
+
+        let state = parse_transcript(&words, &text, DuplicateMode::Standard).unwrap();
+        assert_eq!(state.transcript(), text);
+        assert_eq!(state.candidates, vec![answer]);
+        assert!(parse_transcript(&words, "1. crane GBX", DuplicateMode::Standard).is_err());
+    }
+
+    #[test]
+    fn every_wrong_length_line_is_reported() {
+        let data = "crane\ncran\n\nslates\nabbey\nx\n";
+        assert_eq!(
+            validate_lengths(data, WORD_LENGTH),
+            Err(vec![
+                (2, "cran".to_string()),
+                (4, "slates".to_string()),
+                (6, "x".to_string())
+            ])
+        );
+        assert_eq!(validate_lengths("crane\n\nabbey\n", WORD_LENGTH), Ok(()));
+
+        let path = env::temp_dir().join("wordle-test-lengths.txt");
+        let path = path.to_string_lossy();
+        fs::write(&*path, data).unwrap();
+        let err = load_words(&path).unwrap_err();
+        fs::remove_file(&*path).unwrap();
+        let msg = err.to_string();
+        assert!(msg.contains("line 2") && msg.contains("line 4") && msg.contains("line 6"));
+    }
+
+    #[test]
+    fn greens_and_absent_letters_build_factifys_facts() {
+        let facts = greens_facts("_r_n_", "stlo").unwrap();
+        assert_eq!(facts, factify(&[('r', 1), ('n', 3)], &[], "stlo"));
+        assert_eq!(
+            greens_facts(" ..A.. ", "").unwrap(),
+            factify(&[('a', 2)], &[], "")
+        );
+        assert!(greens_facts("_r_n", "").is_err());
+    }
+
+    #[test]
+    fn a_guess_with_the_five_commonest_letters_covers_five() {
+        let words = parse_words(DEFAULT_WORDS);
+        let config = Config::default();
+        let freqs = letter_frequencies(&words, &config);
+        let mut letters: Vec<char> = ASCII_LOWER.to_vec();
+        letters.sort_by_key(|&c| Reverse(freqs[letter_index(c).unwrap()]));
+        let top: String = letters[..COMMON_LETTERS].iter().collect();
+        assert_eq!(common_letter_coverage(&freqs, &to_array(&top), &config), 5);
+        assert_eq!(
+            common_letter_coverage(&freqs, &to_array("orate"), &config),
+            5
+        );
+        assert_eq!(
+            common_letter_coverage(&freqs, &to_array("fuzzy"), &config),
+            0
+        );
+    }
+
+    #[test]
+    fn position_groups_cover_the_candidates_under_their_letter() {
+        let candidates = parse_words("batch\nlatch\nmatch\npatch\nwatch\nhutch\nbitch\n");
+        let groups = group_by_position(&candidates, 1);
+        let keys: Vec<char> = groups.iter().map(|(l, _)| *l).collect();
+        assert_eq!(keys, ['a', 'i', 'u']);
+        assert_eq!(groups[0].1.len(), 5);
+        for (l, g) in &groups {
+            assert!(g.iter().all(|w| w[1] == *l));
+        }
+        assert_eq!(
+            groups.iter().map(|(_, g)| g.len()).sum::<usize>(),
+            candidates.len()
+        );
+    }
+
+    #[test]
+    fn the_distinguisher_for_an_atch_cluster_tests_the_first_letters() {
+        let words = parse_words(DEFAULT_WORDS);
+        let candidates = parse_words("batch\ncatch\nhatch\nlatch\nmatch\npatch\nwatch\n");
+        let g = minimal_distinguisher(&words, &candidates);
+        let touched = "bchlmpw".chars().filter(|c| g.contains(c)).count();
+        assert!(touched >= 3, "{}", word_str(&g));
+        let groups = evaluate_guess(&candidates, &g).groups;
+        assert!(candidates
+            .iter()
+            .all(|c| evaluate_guess(&candidates, c).groups <= groups));
+    }
+
+    #[test]
+    fn a_run_report_serializes_its_key_fields() {
+        let report = RunReport {
+            strategy: Strategy::Minimax,
+            opener: to_array("crane"),
+            distribution: vec![1, 5, 10],
+            timings: vec![("opener", Duration::from_millis(250))],
+            dictionary_hash: 0xabc,
+        };
+        let json = report.to_json();
+        assert!(json.contains("\"strategy\": \"Minimax\""), "{}", json);
+        assert!(json.contains("\"opener\": \"crane\""));
+        assert!(json.contains("\"distribution\": [1, 5, 10]"));
+        assert!(json.contains("\"opener\": 0.250000"));
+        assert!(json.contains("\"dictionary_hash\": \"0000000000000abc\""));
+
+        let words = answers(20);
+        let run = RunReport::run(&words, Strategy::Entropy, None).unwrap();
+        assert_eq!(run.distribution.iter().sum::<usize>(), words.len());
+        assert_eq!(run.dictionary_hash, dictionary_hash(&words));
+        assert_eq!(run.timings.len(), 2);
+    }
+
+    #[test]
+    fn the_inferred_pattern_is_checks_colors() {
+        let (answer, guess) = (to_array("about"), to_array("adore"));
+        let facts = check(&answer, &guess, DuplicateMode::Standard);
+        let p = facts_to_pattern(&facts).unwrap();
+        assert_eq!(p, pattern(&answer, &guess));
+        assert_eq!(pattern_string(p), "GBGBB");
+        assert_eq!(parse_feedback("adore", "GBGBB").unwrap(), facts);
+    }
+
+    #[test]
+    fn conflicting_greens_name_the_position_and_letters() {
+        let mut facts = parse_feedback("crane", "BGBBB").unwrap();
+        assert_eq!(validate_facts(&facts), Ok(()));
+        facts.extend(parse_feedback("plumb", "BGBBB").unwrap());
+        assert_eq!(
+            validate_facts(&facts),
+            Err("position 2 can't be both 'r' and 'l'".to_string())
+        );
+        // the same green twice is no conflict
+        facts.truncate(WORD_LENGTH);
+        facts.extend(parse_feedback("fryer", "BGBBB").unwrap());
+        assert_eq!(validate_facts(&facts), Ok(()));
+    }
+
+    #[test]
+    fn grading_a_fixed_game_gives_the_colors_and_the_strategys_choice() {
+        let words = answers(40);
+        // the answer --practice --seed 11 draws
+        let answer = words[SplitMix64(11).below(words.len())];
+
+        let mut candidates = words.clone();
+        let optimal = select_guess(&words, &candidates, Strategy::Entropy).unwrap();
+        let guess = words
+            .iter()
+            .copied()
+            .find(|g| *g != optimal && *g != answer)
+            .unwrap();
+        let (colors, coached) = grade(&words, &candidates, &answer, &guess, Strategy::Entropy);
+        assert_eq!(
+            colors,
+            feedback_colors(&check(&answer, &guess, DuplicateMode::Standard))
+        );
+        assert_eq!(coached, Some(optimal));
+        assert_eq!(
+            grade(&words, &candidates, &answer, &optimal, Strategy::Entropy).1,
+            None
+        );
+
+        let p = pattern(&answer, &guess);
+        candidates.retain(|c| pattern(c, &guess) == p);
+        let (colors, _) = grade(&words, &candidates, &answer, &answer, Strategy::Entropy);
+        assert_eq!(colors, "GGGGG");
+    }
+
+    #[test]
+    fn an_alphabet_is_its_distinct_letters_in_order() {
+        assert_eq!(
+            parse_alphabet("fEdcbaa").unwrap(),
+            ['a', 'b', 'c', 'd', 'e', 'f']
+        );
+        assert!(parse_alphabet("ab1").is_err());
+    }
+
+    #[test]
+    fn outside_alphabet_lists_words_using_other_letters() {
+        let words = parse_words("decaf\nfaced\nbadge\n");
+        let config = Config::default().alphabet(parse_alphabet("abcdef").unwrap());
+        assert_eq!(outside_alphabet(&words, &config), ["badge"]);
+        assert!(outside_alphabet(&words, &Config::default()).is_empty());
+    }
+
+    #[test]
+    fn the_opener_breakdown_rows_sum_to_the_list() {
+        let words = answers(100);
+        let table = opener_breakdown(&words, &to_array("crane"), DEFAULT_PRECISION);
+        let mut lines = table.lines();
+        assert_eq!(lines.next(), Some("Pattern Remaining Probability"));
+        let rows: Vec<Vec<&str>> = lines.map(|l| l.split_whitespace().collect()).collect();
+        let remaining: Vec<usize> = rows.iter().map(|r| r[1].parse().unwrap()).collect();
+        assert_eq!(remaining.iter().sum::<usize>(), words.len());
+        assert!(remaining.windows(2).all(|p| p[0] >= p[1]));
+        let percent: f64 = rows
+            .iter()
+            .map(|r| r[2].trim_end_matches('%').parse::<f64>().unwrap())
+            .sum();
+        assert!((percent - 100.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn constraints_split_the_colors_into_positions_and_counts() {
+        // eerie against crane: BBYBG
+        let facts = check_str("crane", "eerie");
+        let c = constraints_from_facts(&facts);
+        let i = |l| letter_index(l).unwrap();
+        assert_eq!(c.required, [None, None, None, None, Some('e')]);
+        assert_eq!(
+            c.forbidden,
+            [vec!['e'], vec!['e'], vec!['r'], vec!['i'], vec![]]
+        );
+        assert_eq!((c.min_counts[i('e')], c.min_counts[i('r')]), (1, 1));
+        assert_eq!(c.min_counts[i('i')], 0);
+        assert_eq!(c.max_counts[i('e')], Some(1));
+        assert_eq!(c.max_counts[i('i')], Some(0));
+        assert_eq!(c.max_counts[i('r')], None);
+
+        let words = answers(300);
+        let expected = filter_words(&words, &facts, DuplicateMode::Standard);
+        let kept: Words = words.iter().copied().filter(|w| c.matches(w)).collect();
+        assert_eq!(kept, expected);
+        assert!(c.matches(&to_array("crane")));
+    }
+
+    #[test]
+    fn hard_mode_never_beats_free_play() {
+        let words = answers(40);
+        for opener in words.iter().step_by(4) {
+            let penalty = hard_mode_penalty(&words, opener, Strategy::Entropy);
+            assert!(penalty >= 0.0, "{} {}", word_str(opener), penalty);
+        }
+    }
+
+    #[test]
+    fn a_shared_emoji_grid_parses_into_feedback_rows() {
+        use Feedback::*;
+        let grid = "Wordle 1,000 2/6\n\n\u{2b1b}\u{1f7e8}\u{2b1b}\u{1f7e9}\u{2b1b}\n\u{1f7e9}\u{1f7e9}\u{1f7e9}\u{1f7e9}\u{1f7e9}\n";
+        assert_eq!(
+            parse_emoji_grid(grid),
+            vec![
+                [NotUsed, Used, NotUsed, Correct, NotUsed],
+                [Correct; WORD_LENGTH]
+            ]
+        );
+        // high contrast colors and light mode's white squares
+        assert_eq!(
+            parse_emoji_grid("\u{1f7e7}\u{1f7e6}\u{2b1c}\u{2b1c}\u{1f7e7}"),
+            vec![[Correct, Used, NotUsed, NotUsed, Correct]]
+        );
+    }
+
+    #[test]
+    fn repeats_group_by_their_shape() {
+        let words = parse_words("eerie\nabbey\ncrane\nmamma\nkebab\nvivid\n");
+        let groups = group_by_repeats(&words);
+        let shapes: Vec<(&str, Words)> = groups
+            .iter()
+            .map(|(s, g)| (s.as_str(), g.clone()))
+            .collect();
+        assert_eq!(
+            shapes,
+            [
+                ("2", parse_words("abbey\nkebab")),
+                ("3", parse_words("eerie")),
+                ("3+2", parse_words("mamma")),
+                ("2+2", parse_words("vivid")),
+            ]
+        );
+    }
+
+    #[test]
+    fn a_template_with_a_fixed_separator_solves() {
+        let words = parse_words("ab_cd\nab_ce\nba_cd\nca_eb\nde_ab\ned_ba\nac_bd\nbd_ca\n");
+        assert_eq!(separator_positions(&words), Ok(vec![2]));
+        assert_eq!(
+            separator_positions(&parse_words("ab_cd\nabc_d\n")),
+            Err(vec!["abc_d".to_string()])
+        );
+        // the separator is always shown green and never narrows anything on its own
+        let facts = check(&words[0], &words[3], DuplicateMode::Standard);
+        assert_eq!(facts[2].feedback, Feedback::Correct);
+        assert_eq!(
+            parse_feedback("ab_cd", "BBBBB").unwrap()[2].feedback,
+            Feedback::Correct
+        );
+
+        let opener = best_guess(&words, &Vec::new()).unwrap().guess;
+        for answer in &words {
+            let line = simulate(&words, answer, &opener, Strategy::Entropy);
+            assert_eq!(line.last(), Some(answer));
+        }
+    }
+
+    #[test]
+    fn letters_missing_from_every_answer_are_reported() {
+        let config = Config::default();
+        assert_eq!(unused_letters(&answers(40), &config), ['j', 'q', 'z']);
+        let themed = parse_words("crane\nslate\nadieu\n");
+        assert!(unused_letters(&themed, &config).contains(&'o'));
+        assert!(unused_letters(&parse_words(DEFAULT_WORDS), &config).is_empty());
+    }
+
+    #[test]
+    fn among_equal_scores_the_green_keeper_is_preferred() {
+        let facts = parse_feedback("adore", "GBBBB").unwrap();
+        let (best, tied) = (
+            GuessEval::from_counts(&[1, 1, 1]),
+            GuessEval::from_counts(&[2, 1]),
+        );
+        let mut ranked = vec![
+            (to_array("fuzzy"), best),
+            (to_array("blitz"), tied.clone()),
+            (to_array("alive"), tied.clone()),
+            (to_array("nymph"), tied),
+        ];
+        prefer_green_keepers(&mut ranked, &facts, Strategy::Entropy);
+        let order: Vec<String> = ranked.iter().map(|(w, _)| word_str(w)).collect();
+        assert_eq!(order, ["fuzzy", "alive", "blitz", "nymph"]);
+        assert!(keeps_greens(&facts, &to_array("alive")));
+        assert!(!keeps_greens(&facts, &to_array("blitz")));
+    }
+
+    #[test]
+    fn diffing_runs_finds_the_one_changed_answer() {
+        let words = answers(6);
+        let before: Vec<GameResult> = words.iter().map(|w| GameResult::new(*w, 3)).collect();
+        let mut after = before.clone();
+        after[4] = GameResult::new(words[4], 5);
+        after.reverse();
+        assert_eq!(diff_runs(&before, &after), vec![(words[4], 3, 5)]);
+        assert!(diff_runs(&before, &before).is_empty());
+    }
+
+    #[test]
+    fn the_runner_up_differs_from_the_best_by_a_non_negative_gap() {
+        let words = answers(50);
+        for strategy in [Strategy::Entropy, Strategy::Expected, Strategy::Minimax] {
+            let (best, second, gap) = select_with_runner_up(&words, &words, strategy).unwrap();
+            assert_ne!(best, second);
+            assert!(gap >= 0.0);
+            assert_eq!(Some(best), select_guess(&words, &words, strategy));
+            let cost = |w: &Word| evaluate_guess(&words, w).cost(strategy);
+            assert_eq!(gap, cost(&second) - cost(&best));
+        }
+        assert!(select_with_runner_up(&words, &Vec::new(), Strategy::Entropy).is_none());
+    }
+
+    #[test]
+    fn the_summary_mentions_every_kind_of_constraint() {
+        // clasp then tarot against party
+        let mut facts = check_str("party", "clasp");
+        facts.extend(check_str("party", "tarot"));
+        assert_eq!(
+            summarize_facts(&facts),
+            "Greens: A at 2, R at 3. Present (not here): P (not 5), T (not 1, 5). \
+             Exactly: 1 T. Absent: C, L, O, S."
+        );
+        assert_eq!(summarize_facts(&Vec::new()), "Nothing known yet.");
+    }
+
+    #[test]
+    fn a_resumed_opener_search_skips_done_openers_and_finds_the_best() {
+        let words = answers(12);
+        let full =
+            search_openers(&words, &words, None, &mut OpenerSearch::default(), |_| {}).unwrap();
+
+        let mut partial = OpenerSearch::default();
+        search_openers(&words, &words[..4].to_vec(), None, &mut partial, |_| {});
+        let mut resumed = OpenerSearch::from_text(&partial.to_text());
+        assert_eq!(resumed.evaluated.len(), 4);
+        let mut searched = Vec::new();
+        let best = search_openers(&words, &words, None, &mut resumed, |s| {
+            searched.push(s.evaluated.last().unwrap().guess)
+        })
+        .unwrap();
+        assert_eq!(searched, words[4..]);
+        assert_eq!((best.guess, best.guesses), (full.guess, full.guesses));
+        assert_eq!(resumed.evaluated.len(), words.len());
+    }
+
+    #[test]
+    fn opener_stats_on_an_atch_cluster() {
+        // catch shows itself and BGGGG for the other three
+        let words = parse_words("batch\ncatch\nhatch\nlatch\n");
+        let stats = opener_pattern_stats(&words, &to_array("catch"));
+        assert_eq!(stats.patterns, 2);
+        assert_eq!(stats.largest, 3);
+        assert_eq!(stats.mean, 2.0);
+        assert_eq!(stats.singletons, 0.25);
+        let expected = -(0.25 * 0.25f64.log2() + 0.75 * 0.75f64.log2());
+        assert!((stats.entropy - expected).abs() < 1e-9);
+
+        // a guess telling all four apart
+        let stats = opener_pattern_stats(&words, &to_array("chlbz"));
+        assert_eq!(stats.patterns, 4);
+        assert!((stats.entropy - 2.0).abs() < 1e-9);
+        assert_eq!(stats.singletons, 1.0);
+    }
+
+    #[test]
+    fn line_analysis_flags_the_turn_a_line_goes_wrong() {
+        let words = answers(60);
+        let opener = select_guess(&words, &words, Strategy::Entropy).unwrap();
+        let answer = *words
+            .iter()
+            .find(|a| simulate(&words, a, &opener, Strategy::Entropy).len() >= 3)
+            .unwrap();
+        let optimal = simulate(&words, &answer, &opener, Strategy::Entropy);
+        let analysis = analyze_line(&words, &answer, &optimal, Strategy::Entropy);
+        assert_eq!(analysis.divergence, None);
+        assert!(analysis.solved);
+        assert_eq!(analysis.turns.len(), optimal.len());
+
+        let bad = *words
+            .iter()
+            .find(|w| **w != optimal[1] && **w != answer)
+            .unwrap();
+        let analysis = analyze_line(&words, &answer, &[opener, bad], Strategy::Entropy);
+        assert_eq!(analysis.divergence, Some(1));
+        assert!(!analysis.solved);
+        assert_eq!(analysis.turns[1].best, Some(optimal[1]));
+        assert!(analysis.to_string().contains("<- first divergence"));
+    }
+
+    #[test]
+    fn with_one_guess_left_only_a_candidate_is_suggested() {
+        let words = answers(200);
+        let facts = check_str("about", "crane");
+        let candidates = filter_words(&words, &facts, DuplicateMode::Standard);
+        assert!(candidates.len() > 2);
+        let last = adaptive_guess(&words, &candidates, 1);
+        assert!(candidates.contains(&last.guess));
+        for sample in [answers(12), words[100..130].to_vec()] {
+            assert!(sample.contains(&adaptive_guess(&words, &sample, 1).guess));
+        }
+        assert_eq!(adaptive_alpha(1), 0.0);
+        assert_eq!(adaptive_alpha(MAX_TURNS), 1.0);
+    }
+
+    #[test]
+    fn a_cluster_no_guess_can_split_is_a_dead_end() {
+        let cluster = parse_words("batch\nlatch\nmatch\npatch\nwatch\n");
+        // guessing one of them only ever tells whether it was that one
+        match choose_guess(&cluster, &cluster, Strategy::Entropy) {
+            Some(Choice::DeadEnd(g)) => assert!(cluster.contains(&g)),
+            other => panic!("{:?}", other),
+        }
+        // outside words can split it
+        let words = parse_words(DEFAULT_WORDS);
+        assert!(matches!(
+            choose_guess(&words, &cluster, Strategy::Entropy),
+            Some(Choice::Guess(_))
+        ));
+    }
+
+    #[test]
+    fn every_tied_opener_is_returned() {
+        // each guess only tells whether it was the answer, so all four tie
+        let cluster = parse_words("batch\nlatch\nmatch\npatch\n");
+        for strategy in [Strategy::Entropy, Strategy::Expected, Strategy::Minimax] {
+            let mut tied = all_optimal_openers(&cluster, strategy);
+            tied.sort();
+            assert_eq!(tied, cluster);
+        }
+        // clamp singles out every word, leaving nothing to tie with
+        let mut words = cluster.clone();
+        words.push(to_array("clamp"));
+        assert_eq!(
+            all_optimal_openers(&words, Strategy::Entropy),
+            [to_array("clamp")]
+        );
+    }
+
+    #[test]
+    fn the_embedded_list_has_every_answer() {
+        let words = parse_words(DEFAULT_WORDS);
+        assert_eq!(words.len(), 2315);
+        assert_eq!(words[0], to_array("aback"));
+        assert_eq!(words[words.len() - 1], to_array("zonal"));
+        assert_eq!(validate_lengths(DEFAULT_WORDS, WORD_LENGTH), Ok(()));
+    }
+
+    #[test]
+    fn only_a_worse_guess_than_the_strategys_has_regret() {
+        let words = answers(60);
+        let best = select_guess(&words, &words, Strategy::Entropy).unwrap();
+        assert_eq!(guess_regret(&words, &words, &best, Strategy::Entropy), 0.0);
+        // fuzzy barely splits the list
+        let regret = guess_regret(&words, &words, &to_array("fuzzy"), Strategy::Entropy);
+        assert!(regret > 0.0, "{}", regret);
+    }
+
+    #[test]
+    fn starter_pairs_leave_at_most_the_threshold() {
+        let words = answers(40);
+        let worst = |a: &Word, b: &Word| {
+            let mut groups: HashMap<(Pattern, Pattern), usize> = HashMap::new();
+            for w in &words {
+                *groups.entry((pattern(w, a), pattern(w, b))).or_default() += 1;
+            }
+            groups.into_values().max().unwrap()
+        };
+        let pairs = good_starter_pairs(&words, 2);
+        assert!(!pairs.is_empty());
+        assert!(pairs.iter().all(|(a, b)| worst(a, b) <= 2));
+        // every qualifying pair is found, each once
+        let all: Vec<(Word, Word)> = (0..words.len())
+            .flat_map(|i| (i + 1..words.len()).map(move |j| (i, j)))
+            .map(|(i, j)| (words[i], words[j]))
+            .filter(|(a, b)| worst(a, b) <= 2)
+            .collect();
+        assert_eq!(pairs, all);
+    }
+
+    #[test]
+    fn a_guess_of_known_absent_letters_gives_no_new_information() {
+        let words = answers(200);
+        let mut state = GameState::new(&words);
+        assert!(state.apply(to_array("crane"), check_str("about", "crane")));
+        let before = state.candidates.clone();
+        // c, r, n and e are already known to be absent
+        let guess = to_array("recce");
+        assert!(!state.apply(guess, check_str("about", "recce")));
+        assert_eq!(state.candidates, before);
+        assert_eq!(state.turns.len(), 2);
+    }
+
+    #[test]
+    fn binary_word_lists_round_trip_and_check_the_length() {
+        let words = parse_words(DEFAULT_WORDS);
+        let path = env::temp_dir().join("wordle-test-words.bin");
+        let path = path.to_string_lossy();
+        save_words_bin(&words, &path).unwrap();
+        assert_eq!(load_words_bin(&path).unwrap(), words);
+        // load_words spots the header too
+        assert_eq!(load_words(&path).unwrap(), words);
+        fs::remove_file(&*path).unwrap();
+
+        let mut bytes = words_to_bin(&words[..3].to_vec()).unwrap();
+        assert_eq!(bytes.len(), WORDS_BIN_HEADER + 3 * WORD_LENGTH);
+        bytes[4] = 6;
+        assert!(words_from_bin(&bytes).is_err());
+        bytes[4] = WORD_LENGTH as u8;
+        bytes.pop();
+        assert!(words_from_bin(&bytes).is_err());
+    }
+
+    #[test]
+    fn exploit_plays_a_candidate_and_explore_may_not() {
+        let words = parse_words(DEFAULT_WORDS);
+        let cluster = parse_words("batch\nlatch\nmatch\npatch\nwatch\n");
+        let exploit = best_exploit(&words, &cluster);
+        let explore = best_explore(&words, &cluster);
+        assert!(cluster.contains(&exploit.guess));
+        assert!(!cluster.contains(&explore.guess));
+        let groups = |g: &Word| evaluate_guess(&cluster, g).groups;
+        assert!(groups(&explore.guess) > groups(&exploit.guess));
+        assert_eq!(explore_guess(&words, &Vec::new()), None);
+    }
+
+    #[test]
+    fn a_time_budget_reports_only_the_answers_played() {
+        let words = answers(100);
+        let opener = to_array("crane");
+        let played =
+            |deadline| solve_all_par_until(&words, &words, &opener, Strategy::Entropy, deadline);
+        let none = played(Some(Instant::now()));
+        assert_eq!(none.num_candidates, 0);
+        assert!(none.depths.is_empty());
+        assert!(none.partition_sizes.is_empty());
+
+        let all = played(None);
+        assert_eq!(all.num_candidates, words.len());
+        assert_eq!(par_distribution(&all.depths), all.distribution());
+        assert_eq!(all.partition_sizes.iter().sum::<usize>(), words.len());
+    }
+
+    #[test]
+    fn a_weak_opener_under_a_tight_limit_reports_its_failures() {
+        let words = answers(60);
+        let gr = solve_all_hard(&words, &to_array("fuzzy"), Strategy::Entropy);
+        let max_turns = 3;
+        let failed = unsolvable_answers(&gr, max_turns);
+        assert!(!failed.is_empty());
+        assert!(failed
+            .iter()
+            .all(|r| r.guesses > max_turns && r.outcome == GameOutcome::Failed));
+        let (hardest, most) = *gr.depths.iter().max_by_key(|(_, d)| *d).unwrap();
+        assert!(failed.contains(&GameResult::within(hardest, most, max_turns)));
+        let expected = gr.depths.iter().filter(|(_, d)| *d > max_turns).count();
+        assert_eq!(failed.len(), expected);
+    }
+
+    #[test]
+    fn displayed_scores_take_the_precision_from_the_format() {
+        let words = answers(50);
+        let eval = evaluate_guess(&words, &to_array("crane"));
+        let entropy = |s: String| s.split(' ').nth(1).unwrap().to_string();
+        assert_eq!(entropy(format!("{}", eval)), format!("{:.4}", eval.entropy));
+        assert_eq!(
+            entropy(format!("{:.2}", eval)),
+            format!("{:.2}", eval.entropy)
+        );
+    }
+
+    #[test]
+    fn best_guess_is_none_when_no_word_matches() {
+        let words = answers(12);
+        let facts = factify(&[], &[], "abcde");
+        assert!(filter_words(&words, &facts, DuplicateMode::Standard).is_empty());
+        assert!(best_guess(&words, &facts).is_none());
+        assert!(best_guess_capped(&words, &facts, Some(1)).is_none());
+    }
+}
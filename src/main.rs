@@ -1,261 +1,1062 @@
-use rayon::prelude::*;
-use std::convert::TryInto;
+use std::cmp::Reverse;
+use std::env;
 use std::fmt;
 use std::fs;
-use std::time::Instant;
-
-const NUM_CHARS: usize = 26;
-const WORD_LENGTH: usize = 5;
-static ASCII_LOWER: [char; NUM_CHARS] = [
-    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's',
-    't', 'u', 'v', 'w', 'x', 'y', 'z',
-];
-
-#[derive(Clone, Debug)]
-enum Feedback {
-    Correct,
-    Used,
-    NotUsed,
-}
-
-#[derive(Clone, Debug)]
-struct Fact {
-    letter: char,
-    position: usize,
-    feedback: Feedback,
-}
+use std::io::{self, BufRead, Write};
+use std::process;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use wordle_rust::*;
 
-type Word = [char; WORD_LENGTH];
-type Words = Vec<Word>;
-type Facts = Vec<Fact>;
-
-fn build_fact(f: Feedback, l: char, p: usize) -> Fact {
-    Fact {
-        letter: l,
-        position: p,
-        feedback: f,
+fn show_state(state: &GameState, suggestion: Option<&Word>, precision: usize) {
+    print!(
+        "{} candidates, remaining entropy: {:.p$} bits",
+        state.candidates.len(),
+        state.remaining_entropy(),
+        p = precision
+    );
+    if let Some(w) = suggestion {
+        print!(", suggestion: {}", word_str(w));
     }
+    println!();
 }
 
-#[derive(Clone, Debug)]
-struct GuessResult {
-    guess: Word,
-    guesses: usize,
-    num_candidates: usize,
-}
-
-impl fmt::Display for GuessResult {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let s: String = self.guess.iter().collect();
-        write!(
-            f,
-            "Word: {:?} Guesses: {} Num: {}",
-            s, self.guesses, self.num_candidates
-        )
+fn print_ranking(ranked: &[(Word, GuessEval)], precision: usize) {
+    for (w, eval) in ranked {
+        println!("{} {:.p$}", word_str(w), eval, p = precision);
     }
 }
 
-fn check(answer: &Word, guess: &Word) -> Facts {
-    let mut res: Facts = Vec::new();
-    for i in 0..WORD_LENGTH {
-        if guess[i] == answer[i] {
-            res.push(build_fact(Feedback::Correct, guess[i], i));
-        } else if answer.contains(&guess[i]) {
-            res.push(build_fact(Feedback::Used, guess[i], i))
-        } else {
-            res.push(build_fact(Feedback::NotUsed, guess[i], i))
-        }
+// each pattern the guess can show with the candidates behind it, largest group first
+fn show_partitions(candidates: &Words, guess: &Word) {
+    for (p, g) in sorted_partitions(candidates, guess) {
+        let names: Vec<String> = g.iter().map(word_str).collect();
+        println!("{} {:>4} {}", pattern_string(p), g.len(), names.join(" "));
     }
-    res
 }
 
-fn to_array(s: &str) -> Word {
-    s.chars().collect::<Vec<_>>().as_slice().try_into().unwrap()
-}
+const PRACTICE_TURNS: usize = 6;
 
-fn check_str(answer: &str, guess: &str) -> Facts {
-    check(&to_array(answer), &to_array(guess))
+// the player guesses an answer drawn with the seed, coach adds the strategy's choice
+fn practice(words: &Words, strategy: Strategy, seed: u64, coach: bool) {
+    let answer = words[SplitMix64(seed).below(words.len())];
+    let mut candidates = words.clone();
+    let stdin = io::stdin();
+    let mut turns = 0;
+    while turns < PRACTICE_TURNS {
+        print!("> ");
+        io::stdout().flush().unwrap();
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).unwrap() == 0 {
+            break;
+        }
+        let guess = match parse_word("guess", &line) {
+            Ok(g) if words.contains(&g) => g,
+            Ok(g) => {
+                println!("{} is not in the list", word_str(&g));
+                continue;
+            }
+            Err(e) => {
+                println!("{}", e);
+                continue;
+            }
+        };
+        turns += 1;
+        let (colors, optimal) = grade(words, &candidates, &answer, &guess, strategy);
+        println!("{}", colors);
+        if coach {
+            if let Some(g) = optimal {
+                println!("optimal would have been {}", word_str(&g));
+            }
+        }
+        if guess == answer {
+            println!("Solved in {}", turns);
+            return;
+        }
+        let p = pattern(&answer, &guess);
+        candidates.retain(|c| pattern(c, &guess) == p);
+    }
+    println!("The answer was {}", word_str(&answer));
 }
 
-fn filter_words(words: &Words, facts: &Facts) -> Words {
-    let mut filtered: Words = Vec::new();
-    words
-        .iter()
-        .filter(|w| {
-            !facts.iter().any(|f| match &f.feedback {
-                Feedback::Correct => w[f.position] != f.letter,
-                Feedback::Used => w[f.position] == f.letter || !w.contains(&f.letter),
-                Feedback::NotUsed => w.contains(&f.letter),
-            })
-        })
-        .for_each(|w| filtered.push(*w));
-    filtered
-}
+// reads "<guess> <colors>" lines from stdin, suggesting a guess from pool before each
+// turn; first_guess is suggested for the opening turn without being scored, sample is
+// --guess-sample's size and seed and mode how the entered colors treat repeats
+fn interactive(
+    words: &Words,
+    pool: &Words,
+    strategy: Strategy,
+    first_guess: Option<Word>,
+    sample: Option<(usize, u64)>,
+    mode: DuplicateMode,
+    precision: usize,
+) {
+    let mut state = GameState::new(words).duplicates(mode);
+    let stdin = io::stdin();
+    // the last suggestion, kept when a turn leaves the candidates as they were
+    let mut unchanged: Option<Word> = None;
+    loop {
+        let suggestion = match (unchanged.take(), first_guess, sample) {
+            (Some(g), _, _) => Some(g),
+            (_, Some(g), _) if state.turns.is_empty() => Some(g),
+            (_, _, Some(sample)) => {
+                select_guess_sampled(pool, &state.candidates, strategy, sample, state.turns.len())
+            }
+            _ => match choose_guess(pool, &state.candidates, strategy) {
+                Some(Choice::DeadEnd(g)) => {
+                    println!(
+                        "These {} words are indistinguishable without luck: {}",
+                        state.candidates.len(),
+                        state
+                            .candidates
+                            .iter()
+                            .map(word_str)
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    );
+                    Some(g)
+                }
+                choice => choice.map(|c| c.guess()),
+            },
+        };
+        show_state(&state, suggestion.as_ref(), precision);
+        if let (Some(exploit), Some(explore)) = (
+            exploit_guess(&state.candidates),
+            explore_guess(pool, &state.candidates),
+        ) {
+            println!(
+                "Exploit: {} Explore: {}",
+                word_str(&exploit),
+                word_str(&explore)
+            );
+        }
+        print!("> ");
+        io::stdout().flush().unwrap();
 
-// exhaustive search for the word which minimizes the number of guesses
-// TODO - add a check to prevent the search from going too deep
-fn best_guess(words: &Words, facts: &Facts) -> GuessResult {
-    let candidates: Words = filter_words(words, facts);
-    if candidates.len() == 1 {
-        GuessResult {
-            guess: candidates[0],
-            guesses: 1,
-            num_candidates: candidates.len(),
-        }
-    } else if candidates.is_empty() {
-        panic!();
-    } else {
-        candidates
-            .par_iter()
-            .map(|g: &Word| {
-                let gs = candidates
-                    .iter()
-                    .map(|w: &Word| {
-                        let mut new_facts: Facts = check(w, g);
-                        let mut prev_facts: Facts = facts.to_vec();
-                        new_facts.append(&mut prev_facts);
-
-                        best_guess(&candidates, &new_facts)
-                    })
-                    .fold(0, |sum, item| sum + item.guesses);
-
-                GuessResult {
-                    guess: *g,
-                    guesses: 1 + gs,
-                    num_candidates: candidates.len(),
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).unwrap() == 0 {
+            break;
+        }
+        if line.trim() == "undo" {
+            if !state.undo() {
+                println!("Nothing to undo");
+            }
+            continue;
+        }
+        match parse_turn(&line) {
+            Ok((guess, feedback)) => {
+                let solved = is_solved(&feedback);
+                let narrowed = state.apply(guess, feedback);
+                if solved {
+                    show_state(&state, None, precision);
+                    println!("Solved in {}", state.turns.len());
+                    print!("{}", state.transcript());
+                    break;
                 }
-            })
-            .reduce_with(|best_guess, gr| {
-                if gr.guesses < best_guess.guesses {
-                    gr
+                if let Err(e) = validate_facts(&state.facts) {
+                    println!("{}", e);
+                    println!("Enter undo to take back the last turn");
+                } else if state.candidates.is_empty() {
+                    println!("No words match these clues, did you mistype a color?");
+                    println!("Enter undo to take back the last turn");
+                } else if !narrowed {
+                    println!("That guess gave no new information");
+                    unchanged = suggestion.filter(|g| *g != guess);
                 } else {
-                    best_guess
+                    println!("{}", summarize_facts(&state.facts));
                 }
-            })
-            .unwrap()
+            }
+            Err(e) => println!("{}", e),
+        }
     }
 }
 
-// exhaustive search using best_guess, will return the number of guesses for each word
-fn solve(words: &Words, guesses: &Words) -> Vec<GuessResult> {
-    guesses
-        .iter()
-        .map(|g| {
-            let gs = words
-                .iter()
-                .map(|w| {
-                    let fs = check(w, g);
-                    best_guess(words, &fs)
-                })
-                .fold(0, |sum, item| sum + item.guesses);
-
-            GuessResult {
-                guess: *g,
-                guesses: 1 + gs,
-                num_candidates: guesses.len(),
-            }
-        })
-        .collect()
+#[derive(Debug, Default)]
+struct Options {
+    heatmap: Option<String>,
+    save_bin: Option<String>,
+    top: Option<usize>,
+    streaming: bool,
+    candidates: Option<String>,
+    strategy: Strategy,
+    collapse: bool,
+    guess_pool_top: Option<usize>,
+    solve: Option<usize>,
+    explain: bool,
+    facts: Facts,
+    // turns given with --feedback, --greens and --absent facts aren't a turn of their own
+    feedback_rows: usize,
+    // letter counts from --exact, which aren't colors so they're kept apart from facts
+    exact: Vec<ExactCount>,
+    guaranteed_two: bool,
+    filter_regex: Option<WordRegex>,
+    unsorted: bool,
+    interactive: bool,
+    tui: bool,
+    tree_dot: Option<String>,
+    tree_depth: Option<usize>,
+    duplicates: DuplicateMode,
+    checkpoint: Option<String>,
+    resume: Option<String>,
+    opener_search: Option<String>,
+    tree_stats: Option<Word>,
+    quiet: bool,
+    prefix: Option<String>,
+    difficulty: Option<Word>,
+    new_letters: bool,
+    keep_greens: bool,
+    daily: bool,
+    words: Vec<String>,
+    // guesses allowed beyond the answers, e.g. data/wordle-allowed-guesses.txt; the
+    // suggestions for the candidates left are drawn from both lists
+    allowed: Option<String>,
+    // only guess words from the answer list, for clones taking no other guesses;
+    // overrides --allowed, and without --allowed it's how every run plays
+    closed: bool,
+    greedy: Option<usize>,
+    greedy_sample: Option<usize>,
+    guess_sample: Option<usize>,
+    runner_up: bool,
+    // unset, practice picks from the clock and sampling uses 0
+    seed: Option<u64>,
+    debug_guess: Option<Word>,
+    prefer_order: bool,
+    time_budget: Option<Duration>,
+    report: Option<String>,
+    first_guess: Option<Word>,
+    compare_guess: Option<Word>,
+    validate: Option<Word>,
+    expect_avg: Option<f64>,
+    emoji: Option<String>,
+    hard_traps: bool,
+    // play --unsolvable in hard mode
+    hard: bool,
+    unsolvable: Option<Word>,
+    max_turns: Option<usize>,
+    budget_ms: Option<usize>,
+    opener_breakdown: Option<Word>,
+    pattern_stats: Option<Word>,
+    config: Config,
+    practice: bool,
+    coach: bool,
+    guesses: Words,
+    answer: Option<Word>,
+    // compare the --guess line with the strategy's play against --answer
+    analyze_line: bool,
+    run_report: bool,
+    report_json: Option<String>,
+    distinguish: bool,
+    explore_exploit: bool,
+    all_optimal: bool,
+    // the worst case --starter-pairs allows after the two guesses
+    starter_pairs: Option<usize>,
+    flexible: bool,
+    // guesses left in the game, for adaptive_guess
+    remaining_guesses: Option<usize>,
+    unused_letters: bool,
+    format: Format,
+    diff_runs: Option<(String, String)>,
+    // 1 based, as players count letters
+    group_by_pos: Option<usize>,
+    list_duplicates: bool,
+    rank_by: Option<RankBy>,
+    greens: Option<String>,
+    absent: String,
+    branch_cap: Option<usize>,
+    precision: Option<usize>,
+    replay: Option<String>,
+    pattern_counts: Option<Word>,
+    optimize: Option<OpenerGoal>,
+    // a game state for the wasm entry point, answered before the list is loaded
+    #[cfg(feature = "wasm")]
+    suggest: Option<String>,
 }
 
-// Greedy algorithm that finds the word that maximizes the most information gain
-// (Reduce the number of remaining possibilities)
-fn greedy(words: &Words) {
-    let mut results = Vec::new();
-    words.iter().take(1).for_each(|guess| {
-        let res = words
-            .iter()
-            .map(|w| {
-                let facts = check(w, guess);
-                filter_words(&words, &facts).len()
-            })
-            .reduce(|sum, item| sum + item)
-            .unwrap();
-
-        results.push(res);
-        println!("{:?}: {:?}", guess, res);
-    });
+fn parse_num(arg: &str, value: &str) -> Result<usize, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{} expects a number, got {:?}", arg, value))
 }
 
-//  WIP Optimization
-fn bits(words: Words) {
-    let mut word_contains: [Vec<bool>; NUM_CHARS] = Default::default();
-    let mut word_contains_not: [Vec<bool>; NUM_CHARS] = Default::default();
-
-    for w in &words {
-        for i in 0..NUM_CHARS {
-            let in_word = w.contains(&ASCII_LOWER[i]);
-            word_contains[i].push(in_word);
-            word_contains_not[i].push(!in_word);
-        }
+fn parse_word(arg: &str, value: &str) -> Result<Word, String> {
+    let value = value.trim().to_lowercase();
+    if value.chars().count() != WORD_LENGTH {
+        return Err(format!(
+            "{} expects a {} letter word, got {:?}",
+            arg, WORD_LENGTH, value
+        ));
     }
+    Ok(to_array(&value))
+}
 
-    let mut position_at: [[Vec<bool>; WORD_LENGTH]; NUM_CHARS] = Default::default();
-    let mut position_at_not: [[Vec<bool>; WORD_LENGTH]; NUM_CHARS] = Default::default();
-    for w in &words {
-        for i in 0..NUM_CHARS {
-            for j in 0..WORD_LENGTH {
-                let is_char = w[j] == ASCII_LOWER[i];
-                position_at[i][j].push(is_char);
-                position_at_not[i][j].push(!is_char);
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut opts = Options::default();
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        let mut value = || {
+            it.next()
+                .cloned()
+                .ok_or(format!("{} requires a value", arg))
+        };
+        match arg.as_str() {
+            "--heatmap" => opts.heatmap = Some(value()?),
+            "--save-bin" => opts.save_bin = Some(value()?),
+            "--top" => opts.top = Some(parse_num(arg, &value()?)?),
+            "--streaming" => opts.streaming = true,
+            "--candidates" => opts.candidates = Some(value()?),
+            "--metric" => opts.strategy = value()?.parse()?,
+            "--collapse" => opts.collapse = true,
+            "--guess-pool-top" => opts.guess_pool_top = Some(parse_num(arg, &value()?)?),
+            "--solve" => opts.solve = Some(parse_num(arg, &value()?)?),
+            "--checkpoint" => opts.checkpoint = Some(value()?),
+            "--resume" => opts.resume = Some(value()?),
+            "--opener-search" => opts.opener_search = Some(value()?),
+            "--quiet" => opts.quiet = true,
+            "--prefer-order" => opts.prefer_order = true,
+            "--greedy" => opts.greedy = Some(parse_num(arg, &value()?)?),
+            "--greedy-sample" => opts.greedy_sample = Some(parse_num(arg, &value()?)?),
+            "--guess-sample" => match parse_num(arg, &value()?)? {
+                0 => return Err(format!("{} expects at least 1 guess", arg)),
+                m => opts.guess_sample = Some(m),
+            },
+            "--runner-up" => opts.runner_up = true,
+            "--seed" => opts.seed = Some(parse_num(arg, &value()?)? as u64),
+            "--words" => opts.words.push(value()?),
+            "--allowed" => opts.allowed = Some(value()?),
+            "--closed" => opts.closed = true,
+            "--daily" => opts.daily = true,
+            "--new-letters" => opts.new_letters = true,
+            "--keep-greens" => opts.keep_greens = true,
+            "--prefix" => opts.prefix = Some(value()?.to_lowercase()),
+            "--explain" => opts.explain = true,
+            "--hard-traps" => opts.hard_traps = true,
+            "--hard" => opts.hard = true,
+            "--unsolvable" => opts.unsolvable = Some(parse_word(arg, &value()?)?),
+            "--max-turns" => opts.max_turns = Some(parse_num(arg, &value()?)?),
+            "--distinguish" => opts.distinguish = true,
+            "--explore-exploit" => opts.explore_exploit = true,
+            "--all-optimal" => opts.all_optimal = true,
+            "--starter-pairs" => opts.starter_pairs = Some(parse_num(arg, &value()?)?),
+            "--flexible" => opts.flexible = true,
+            "--remaining-guesses" => match parse_num(arg, &value()?)? {
+                n @ 1..=MAX_TURNS => opts.remaining_guesses = Some(n),
+                n => return Err(format!("{} expects 1 to {}, got {}", arg, MAX_TURNS, n)),
+            },
+            "--unused-letters" => opts.unused_letters = true,
+            "--format" => opts.format = value()?.parse()?,
+            "--diff-runs" => opts.diff_runs = Some((value()?, value()?)),
+            "--guaranteed-two" => opts.guaranteed_two = true,
+            "--filter-regex" => opts.filter_regex = Some(WordRegex::new(&value()?)?),
+            "--unsorted" => opts.unsorted = true,
+            "--interactive" => opts.interactive = true,
+            "--practice" => opts.practice = true,
+            "--coach" => opts.coach = true,
+            "--optimize" => opts.optimize = Some(value()?.parse()?),
+            "--budget-ms" => opts.budget_ms = Some(parse_num(arg, &value()?)?),
+            "--branch-cap" => match parse_num(arg, &value()?)? {
+                0 => return Err(format!("{} expects at least 1 guess", arg)),
+                k => opts.branch_cap = Some(k),
+            },
+            "--precision" => opts.precision = Some(parse_num(arg, &value()?)?),
+            "--rank-by" => opts.rank_by = Some(value()?.parse()?),
+            "--group-by-pos" => match parse_num(arg, &value()?)? {
+                n @ 1..=WORD_LENGTH => opts.group_by_pos = Some(n),
+                n => return Err(format!("{} expects 1 to {}, got {}", arg, WORD_LENGTH, n)),
+            },
+            "--list-duplicates" => opts.list_duplicates = true,
+            "--guess" => opts.guesses.push(parse_word(arg, &value()?)?),
+            "--answer" => opts.answer = Some(parse_word(arg, &value()?)?),
+            "--analyze-line" => opts.analyze_line = true,
+            "--alphabet" => opts.config = opts.config.alphabet(parse_alphabet(&value()?)?),
+            "--greens" => opts.greens = Some(value()?),
+            "--absent" => opts.absent = value()?,
+            "--exact" => opts.exact.push(parse_exact(&value()?)?),
+            "--duplicates" => opts.duplicates = value()?.parse()?,
+            "--tree-dot" => opts.tree_dot = Some(value()?),
+            "--report" => opts.report = Some(value()?),
+            "--run-report" => opts.run_report = true,
+            "--report-json" => {
+                opts.run_report = true;
+                opts.report_json = Some(value()?);
+            }
+            "--first-guess" => opts.first_guess = Some(parse_word(arg, &value()?)?),
+            "--compare-guess" => opts.compare_guess = Some(parse_word(arg, &value()?)?),
+            "--opener-breakdown" => opts.opener_breakdown = Some(parse_word(arg, &value()?)?),
+            "--pattern-counts" => opts.pattern_counts = Some(parse_word(arg, &value()?)?),
+            "--pattern-stats" => opts.pattern_stats = Some(parse_word(arg, &value()?)?),
+            "--emoji" => opts.emoji = Some(value()?),
+            "--validate" => opts.validate = Some(parse_word(arg, &value()?)?),
+            "--expect-avg" => {
+                let v = value()?;
+                let avg = v
+                    .parse()
+                    .map_err(|_| format!("{} expects a number, got {:?}", arg, v))?;
+                opts.expect_avg = Some(avg);
+            }
+            "--time-budget" => {
+                let v = value()?;
+                let secs: f64 = v
+                    .parse()
+                    .map_err(|_| format!("{} expects seconds, got {:?}", arg, v))?;
+                opts.time_budget = Some(
+                    Duration::try_from_secs_f64(secs)
+                        .map_err(|_| format!("{} expects seconds, got {:?}", arg, v))?,
+                );
+            }
+            "--replay" => opts.replay = Some(value()?),
+            "--debug-guess" => opts.debug_guess = Some(parse_word(arg, &value()?)?),
+            "--tree-stats" => opts.tree_stats = Some(parse_word(arg, &value()?)?),
+            "--difficulty" => opts.difficulty = Some(parse_word(arg, &value()?)?),
+            "--tree-depth" => opts.tree_depth = Some(parse_num(arg, &value()?)?),
+            #[cfg(feature = "tui")]
+            "--tui" => opts.tui = true,
+            #[cfg(not(feature = "tui"))]
+            "--tui" => return Err("--tui needs a build with --features tui".to_string()),
+            #[cfg(feature = "wasm")]
+            "--suggest" => opts.suggest = Some(value()?),
+            "--feedback" => {
+                let v = value()?;
+                let (guess, colors) = v
+                    .split_once(':')
+                    .ok_or(format!("--feedback expects guess:colors, got {:?}", v))?;
+                opts.facts.append(&mut parse_feedback(guess, colors)?);
+                opts.feedback_rows += 1;
             }
+            _ => return Err(format!("unknown argument: {}", arg)),
         }
     }
+    if opts.guesses.is_empty() != opts.answer.is_none() {
+        return Err("--guess and --answer go together".to_string());
+    }
+    if opts.analyze_line && opts.answer.is_none() {
+        return Err("--analyze-line needs --guess and --answer".to_string());
+    }
+    if opts.validate.is_some() != opts.expect_avg.is_some() {
+        return Err("--validate and --expect-avg go together".to_string());
+    }
+    if opts.greens.is_some() || !opts.absent.is_empty() {
+        let template = opts.greens.as_deref().unwrap_or(".....");
+        opts.facts
+            .append(&mut greens_facts(template, &opts.absent)?);
+    }
+    Ok(opts)
 }
 
-fn factify(correct: &Vec<(char, usize)>, used: &Vec<(char, usize)>, not_used: &str) -> Facts {
-    let mut facts = Vec::new();
-    correct.iter().for_each(|f| {
-        facts.push(Fact {
-            letter: f.0,
-            position: f.1,
-            feedback: Feedback::Correct,
-        });
-    });
+// the words left by the feedback and any --exact counts
+fn remaining(words: &Words, opts: &Options) -> Words {
+    filter_words(words, &opts.facts, opts.duplicates)
+        .into_iter()
+        .filter(|w| opts.exact.iter().all(|e| e.matches(w)))
+        .collect()
+}
 
-    used.iter().for_each(|f| {
-        facts.push(Fact {
-            letter: f.0,
-            position: f.1,
-            feedback: Feedback::Used,
-        });
-    });
+const EXIT_IO: i32 = 1;
+const EXIT_USAGE: i32 = 2;
+const EXIT_NO_CANDIDATES: i32 = 3;
+// --validate computed a different average than claimed
+const EXIT_MISMATCH: i32 = 4;
 
-    not_used.chars().collect::<Vec<_>>().iter().for_each(|c| {
-        facts.push(Fact {
-            letter: *c,
-            position: 0,
-            feedback: Feedback::NotUsed,
-        });
-    });
+// half the last digit of an average published to two places
+const VALIDATE_TOLERANCE: f64 = 0.005;
 
-    facts
+fn fail(code: i32, msg: impl fmt::Display) -> ! {
+    eprintln!("{}", msg);
+    process::exit(code)
 }
 
 fn main() {
     let start = Instant::now();
 
-    let mut words: Words = Vec::new();
-    {
-        let data = fs::read_to_string("data/wordle-answers-alphabetical.txt").expect("");
-        for l in data.lines() {
-            words.push(to_array(l));
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut opts = parse_args(&args).unwrap_or_else(|e| fail(EXIT_USAGE, e));
+    let precision = opts.precision.unwrap_or(DEFAULT_PRECISION);
+    // --guess with --answer stands in for typing the colors
+    if let Some(answer) = opts.answer {
+        for guess in &opts.guesses {
+            let facts = check(&answer, guess, opts.duplicates);
+            if !opts.quiet {
+                let p = facts_to_pattern(&facts).expect("check gives every color");
+                println!("{} {}", word_str(guess), pattern_string(p));
+            }
+            opts.facts.extend(facts);
         }
     }
+    #[cfg(feature = "wasm")]
+    if let Some(state) = &opts.suggest {
+        println!("{}", wasm::suggest(state));
+        return;
+    }
+    // informational output, dropped by --quiet
+    let info = |msg: String| {
+        if !opts.quiet {
+            println!("{}", msg);
+        }
+    };
 
-    println!("{}", words.len());
+    let mut words: Words = if opts.words.is_empty() {
+        let words = parse_words(DEFAULT_WORDS);
+        info(format!("{}", words.len()));
+        words
+    } else {
+        let lists: Vec<Words> = opts
+            .words
+            .iter()
+            .map(|path| {
+                load_words(path)
+                    .unwrap_or_else(|e| fail(EXIT_IO, format!("failed to read {}: {}", path, e)))
+            })
+            .collect();
+        let total: usize = lists.iter().map(|l| l.len()).sum();
+        let words = merge_words(&lists);
+        info(format!(
+            "{} words, {} after removing duplicates",
+            total,
+            words.len()
+        ));
+        words
+    };
 
-    concise(&words);
+    let outside = outside_alphabet(&words, &opts.config);
+    if !outside.is_empty() {
+        fail(
+            EXIT_IO,
+            format!("words outside the alphabet: {}", outside.join(", ")),
+        );
+    }
+    if let Err(odd) = separator_positions(&words) {
+        fail(
+            EXIT_IO,
+            format!(
+                "words with separators out of place for {}: {}",
+                word_str(&words[0]),
+                odd.join(", ")
+            ),
+        );
+    }
+
+    if let Some(re) = &opts.filter_regex {
+        words.retain(|w| re.is_match(&word_str(w)));
+        info(format!("{} words match the filter", words.len()));
+    }
+    if let Err(e) = validate_facts(&opts.facts) {
+        fail(EXIT_NO_CANDIDATES, e);
+    }
+    // the words suggested guesses come from
+    let pool: Words = match &opts.allowed {
+        Some(path) if !opts.closed => {
+            let allowed = load_words(path)
+                .unwrap_or_else(|e| fail(EXIT_IO, format!("failed to read {}: {}", path, e)));
+            merge_words(&[words.clone(), allowed])
+        }
+        _ => words.clone(),
+    };
+    let constrained = !opts.facts.is_empty() || !opts.exact.is_empty();
+    if constrained && remaining(&words, &opts).is_empty() {
+        fail(EXIT_NO_CANDIDATES, "No words match the given feedback");
+    }
+
+    if let Some(path) = &opts.heatmap {
+        let csv = heatmap_csv(&positional_frequencies(&words), &opts.config);
+        fs::write(path, csv)
+            .unwrap_or_else(|e| fail(EXIT_IO, format!("failed to write {}: {}", path, e)));
+        info(format!("Wrote heatmap to {}", path));
+    } else if let Some(path) = &opts.save_bin {
+        save_words_bin(&words, path)
+            .unwrap_or_else(|e| fail(EXIT_IO, format!("failed to write {}: {}", path, e)));
+        info(format!("Wrote {} words to {}", words.len(), path));
+    } else if let Some(RankBy::Coverage) = opts.rank_by {
+        let freqs = letter_frequencies(&words, &opts.config);
+        let mut ranked: Vec<(Word, usize)> = words
+            .iter()
+            .map(|w| (*w, common_letter_coverage(&freqs, w, &opts.config)))
+            .collect();
+        ranked.sort_by_key(|(w, n)| {
+            (
+                Reverse(*n),
+                Reverse(frequency_score(&freqs, w, &opts.config)),
+            )
+        });
+        for (w, n) in ranked.iter().take(opts.top.unwrap_or(10)) {
+            println!("{} {}", word_str(w), n);
+        }
+    } else if let Some(n) = opts.top {
+        let table = PatternTable::new(&words, opts.streaming);
+        let candidates: Vec<usize> = candidate_indices(&words, &opts.facts, opts.duplicates)
+            .into_iter()
+            .filter(|&i| opts.exact.iter().all(|e| e.matches(&words[i])))
+            .collect();
+        let pool = match opts.guess_pool_top {
+            Some(k) => frequency_pool(&words, k, &opts.config),
+            None => (0..words.len()).collect(),
+        };
+        let mut ranked = rank_guesses(&table, &pool, &candidates, opts.strategy);
+        if opts.new_letters {
+            sort_by_new_letters(&mut ranked, &opts.facts);
+        }
+        if opts.keep_greens {
+            prefer_green_keepers(&mut ranked, &opts.facts, opts.strategy);
+        }
+        let remaining: Words = candidates.iter().map(|&i| words[i]).collect();
+        if opts.collapse {
+            print_ranking(
+                &collapse_equivalent(&ranked, &remaining, n, opts.strategy, opts.prefer_order),
+                precision,
+            );
+        } else {
+            print_ranking(&ranked[..n.min(ranked.len())], precision);
+        }
+        if opts.explain && !ranked.is_empty() {
+            println!("{}", explain_guess(&remaining, &ranked[0].0, precision));
+        }
+    } else if let Some(path) = &opts.candidates {
+        let openers = load_words(path)
+            .unwrap_or_else(|e| fail(EXIT_IO, format!("failed to read {}: {}", path, e)));
+        let remaining = remaining(&words, &opts);
+        let mut ranked = rank_openers(&remaining, &openers, opts.strategy);
+        if opts.new_letters {
+            sort_by_new_letters(&mut ranked, &opts.facts);
+        }
+        if opts.keep_greens {
+            prefer_green_keepers(&mut ranked, &opts.facts, opts.strategy);
+        }
+        print_ranking(&ranked, precision);
+        if opts.explain && !ranked.is_empty() {
+            println!("{}", explain_guess(&remaining, &ranked[0].0, precision));
+        }
+    } else if let Some(n) = opts.solve {
+        let slice = words[..n.min(words.len())].to_vec();
+        let mut res = match &opts.resume {
+            Some(path) => load_checkpoint(path)
+                .unwrap_or_else(|e| fail(EXIT_IO, format!("failed to read {}: {}", path, e))),
+            None => Vec::new(),
+        };
+        let mut file = match opts.resume.as_ref().or(opts.checkpoint.as_ref()) {
+            Some(path) => Some(
+                fs::OpenOptions::new()
+                    .create(true)
+                    .append(opts.resume.is_some())
+                    .write(true)
+                    .truncate(opts.resume.is_none())
+                    .open(path)
+                    .unwrap_or_else(|e| fail(EXIT_IO, format!("failed to open {}: {}", path, e))),
+            ),
+            None => None,
+        };
+        let recorded: Vec<Word> = res.iter().map(|gr| gr.guess).collect();
+        res.extend(solve_shared_each(
+            &slice,
+            &slice,
+            opts.branch_cap,
+            |g| recorded.contains(g),
+            |gr| {
+                if let Some(f) = file.as_mut() {
+                    writeln!(f, "{}", gr.to_csv())
+                        .unwrap_or_else(|e| fail(EXIT_IO, format!("failed to checkpoint: {}", e)));
+                }
+            },
+        ));
+        if !opts.unsorted {
+            sort_results(&mut res, Some(&words).filter(|_| opts.prefer_order));
+        }
+        res.iter().for_each(|gr| println!("{}", gr));
+    } else if let Some(path) = &opts.opener_search {
+        let openers: Words = match opts.guess_pool_top {
+            Some(k) => frequency_pool(&words, k, &opts.config)
+                .iter()
+                .map(|&i| words[i])
+                .collect(),
+            None => words.clone(),
+        };
+        let mut search = load_opener_search(path)
+            .unwrap_or_else(|e| fail(EXIT_IO, format!("failed to read {}: {}", path, e)));
+        info(format!(
+            "{} of {} openers already evaluated",
+            search.evaluated.len(),
+            openers.len()
+        ));
+        let best = search_openers(&words, &openers, opts.branch_cap, &mut search, |s| {
+            save_opener_search(path, s)
+                .unwrap_or_else(|e| fail(EXIT_IO, format!("failed to checkpoint: {}", e)));
+            if let (Some(last), Some(best)) = (s.evaluated.last(), &s.best) {
+                info(format!("{} best so far: {}", last, best.word_str()));
+            }
+        });
+        match best {
+            Some(gr) => println!("Best opener: {}", gr),
+            None => fail(EXIT_NO_CANDIDATES, "No openers to evaluate"),
+        }
+    } else if let Some(n) = opts.greedy {
+        for (w, score) in greedy(&words, n, opts.greedy_sample, opts.seed.unwrap_or(0)) {
+            println!("{}: {}", word_str(&w), score);
+        }
+    } else if let Some(budget) = opts.time_budget {
+        let opener = opts
+            .first_guess
+            .or_else(|| select_guess(&words, &words, opts.strategy))
+            .unwrap_or_else(|| fail(EXIT_NO_CANDIDATES, "No words to play"));
+        let deadline = Instant::now() + budget;
+        let gr = solve_all_par_until(&words, &words, &opener, opts.strategy, Some(deadline));
+        println!("Opener: {}", gr.word_str());
+        println!(
+            "Distribution: {}",
+            format_distribution(&par_distribution(&gr.depths))
+        );
+        println!(
+            "Played {} of {} answers, {} remaining",
+            gr.num_candidates,
+            words.len(),
+            words.len() - gr.num_candidates
+        );
+    } else if let Some(path) = &opts.tree_dot {
+        let remaining = remaining(&words, &opts);
+        let depth = opts.tree_depth.unwrap_or(3);
+        let tree = build_decision_tree(&pool, &remaining, opts.strategy, depth);
+        fs::write(path, tree_dot(&tree))
+            .unwrap_or_else(|e| fail(EXIT_IO, format!("failed to write {}: {}", path, e)));
+        info(format!("Wrote decision tree to {}", path));
+    } else if let Some(prefix) = &opts.prefix {
+        let remaining = remaining(&words, &opts);
+        match best_guess_with_prefix(&pool, &remaining, prefix, opts.strategy) {
+            Some(gr) if opts.quiet => println!("{}", gr.word_str()),
+            Some(gr) => {
+                println!("Best guess: {}", gr);
+                println!("Distribution: {}", format_distribution(&gr.distribution()));
+                println!("Partitions: {}", gr.partition_summary());
+            }
+            None => fail(
+                EXIT_NO_CANDIDATES,
+                format!("No words start with {:?}", prefix),
+            ),
+        }
+    } else if opts.daily {
+        if words.is_empty() {
+            fail(
+                EXIT_USAGE,
+                "--daily needs a word list with at least one word",
+            );
+        }
+        let (number, i) = daily_index(today(), words.len());
+        let answer = words[i];
+        let opener = opts
+            .first_guess
+            .or_else(|| select_guess(&words, &words, opts.strategy))
+            .unwrap();
+        let played = simulate(&words, &answer, &opener, opts.strategy);
+        println!("Wordle {} {}/6", number, played.len());
+        let mut remaining = words.clone();
+        for g in &played {
+            let p = pattern(&answer, g);
+            remaining.retain(|c| pattern(c, g) == p);
+            println!(
+                "{} {} remaining entropy: {:.prec$} bits",
+                pattern_string(p),
+                word_str(g),
+                remaining_entropy(remaining.len()),
+                prec = precision
+            );
+        }
+    } else if let Some(guess) = &opts.compare_guess {
+        let remaining = remaining(&words, &opts);
+        println!(
+            "{}",
+            compare_guess(&pool, &remaining, guess, opts.strategy, precision)
+        );
+    } else if let Some(opener) = &opts.opener_breakdown {
+        print!("{}", opener_breakdown(&words, opener, precision));
+    } else if let Some(opener) = &opts.pattern_stats {
+        println!(
+            "{:.p$}",
+            opener_pattern_stats(&words, opener),
+            p = precision
+        );
+    } else if let Some(guess) = &opts.pattern_counts {
+        for (p, n) in pattern_counts(&remaining(&words, &opts), guess) {
+            println!("{} {}", p, n);
+        }
+    } else if let Some(path) = &opts.emoji {
+        let text = fs::read_to_string(path)
+            .unwrap_or_else(|e| fail(EXIT_IO, format!("failed to read {}: {}", path, e)));
+        for row in parse_emoji_grid(&text) {
+            println!("{}", pattern_string(feedback_pattern(&row)));
+        }
+    } else if let Some(path) = &opts.replay {
+        let text = fs::read_to_string(path)
+            .unwrap_or_else(|e| fail(EXIT_IO, format!("failed to read {}: {}", path, e)));
+        let state = parse_transcript(&words, &text, opts.duplicates)
+            .unwrap_or_else(|e| fail(EXIT_USAGE, e));
+        print!("{}", state.transcript());
+        let suggestion = select_guess(&pool, &state.candidates, opts.strategy);
+        show_state(&state, suggestion.as_ref(), precision);
+    } else if let Some(pos) = opts.group_by_pos {
+        for (l, g) in group_by_position(&remaining(&words, &opts), pos - 1) {
+            let names: Vec<String> = g.iter().map(word_str).collect();
+            println!("{}: {}", l, names.join(" "));
+        }
+    } else if opts.list_duplicates {
+        for (shape, g) in group_by_repeats(&words) {
+            let names: Vec<String> = g.iter().map(word_str).collect();
+            println!("{} ({}): {}", shape, g.len(), names.join(" "));
+        }
+    } else if let Some(guess) = &opts.debug_guess {
+        show_partitions(&remaining(&words, &opts), guess);
+    } else if let Some(opener) = &opts.difficulty {
+        match opts.format {
+            Format::Text => {
+                for (w, n) in rank_answers_by_difficulty(&words, opener, opts.strategy) {
+                    println!("{} {}", word_str(&w), n);
+                }
+            }
+            Format::Jsonl => {
+                let root =
+                    expand_decision_tree(&words, &words, opener, opts.strategy, MAX_TREE_DEPTH);
+                visit_depths(&root, &mut |w, n| {
+                    println!("{}", GameResult::new(w, n).to_json())
+                });
+            }
+        }
+    } else if let Some(goal) = opts.optimize {
+        let openers: Words = rank_openers(&words, &words, opts.strategy)
+            .iter()
+            .take(OPTIMIZE_OPENERS)
+            .map(|(w, _)| *w)
+            .collect();
+        if let Some(gr) = best_opener(&words, &openers, opts.strategy, goal) {
+            println!("Best opener: {}", gr);
+            println!("Distribution: {}", format_distribution(&gr.distribution()));
+            println!("Answers taking {}+: {}", TAIL_GUESSES, tail_count(&gr));
+        }
+    } else if let (Some(opener), Some(expected)) = (opts.validate, opts.expect_avg) {
+        let gr = solve_all(&words, &words, &opener, opts.strategy);
+        let avg = gr.guesses as f64 / gr.num_candidates.max(1) as f64;
+        let ok = (avg - expected).abs() <= VALIDATE_TOLERANCE;
+        println!(
+            "{} averages {:.p$}, expected {}: {}",
+            word_str(&opener),
+            avg,
+            expected,
+            if ok { "ok" } else { "mismatch" },
+            p = precision
+        );
+        if !ok {
+            process::exit(EXIT_MISMATCH);
+        }
+    } else if opts.run_report {
+        let report = RunReport::run(&words, opts.strategy, opts.first_guess)
+            .unwrap_or_else(|| fail(EXIT_NO_CANDIDATES, "No words to play"));
+        match &opts.report_json {
+            Some(path) => {
+                fs::write(path, report.to_json() + "\n")
+                    .unwrap_or_else(|e| fail(EXIT_IO, format!("failed to write {}: {}", path, e)));
+                info(format!("Wrote run report to {}", path));
+            }
+            None => println!("{}", report),
+        }
+    } else if let Some(path) = &opts.report {
+        fs::write(path, opener_report(&words, opts.strategy, precision))
+            .unwrap_or_else(|e| fail(EXIT_IO, format!("failed to write {}: {}", path, e)));
+        info(format!("Wrote opener report to {}", path));
+    } else if let Some(opener) = &opts.tree_stats {
+        println!("{}", tree_stats(&words, opener, opts.strategy));
+    } else if opts.practice {
+        if words.is_empty() {
+            fail(
+                EXIT_USAGE,
+                "--practice needs a word list with at least one word",
+            );
+        }
+        let seed = opts.seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_nanos() as u64)
+                .unwrap_or(0)
+        });
+        practice(&words, opts.strategy, seed, opts.coach);
+    } else if opts.tui {
+        #[cfg(feature = "tui")]
+        tui::run(&words, opts.strategy, opts.duplicates)
+            .unwrap_or_else(|e| fail(EXIT_IO, format!("terminal error: {}", e)));
+    } else if opts.interactive {
+        let sample = opts.guess_sample.map(|m| (m, opts.seed.unwrap_or(0)));
+        interactive(
+            &words,
+            &pool,
+            opts.strategy,
+            opts.first_guess,
+            sample,
+            opts.duplicates,
+            precision,
+        );
+    } else if let Some(opener) = &opts.unsolvable {
+        let gr = if opts.hard {
+            solve_all_hard(&words, opener, opts.strategy)
+        } else {
+            solve_all(&words, &words, opener, opts.strategy)
+        };
+        let max_turns = opts.max_turns.unwrap_or(MAX_TURNS);
+        let failed = unsolvable_answers(&gr, max_turns);
+        for r in &failed {
+            match opts.format {
+                Format::Text => println!("{} {}", word_str(&r.answer), r.guesses),
+                Format::Jsonl => println!("{}", r.to_json()),
+            }
+        }
+        info(format!(
+            "{} of {} answers take more than {} guesses after {}",
+            failed.len(),
+            gr.depths.len(),
+            max_turns,
+            word_str(opener)
+        ));
+    } else if opts.hard_traps {
+        let mut penalties: Vec<(Word, f64)> = rank_openers(&words, &words, opts.strategy)
+            .iter()
+            .take(OPTIMIZE_OPENERS)
+            .map(|(o, _)| (*o, hard_mode_penalty(&words, o, opts.strategy)))
+            .collect();
+        penalties.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        for (o, p) in penalties {
+            println!("{} {:+.*}", word_str(&o), precision, p);
+        }
+    } else if let Some(max_worst) = opts.starter_pairs {
+        let pairs = good_starter_pairs(&words, max_worst);
+        for (a, b) in &pairs {
+            println!("{} {}", word_str(a), word_str(b));
+        }
+        info(format!(
+            "{} pairs leave at most {} candidates",
+            pairs.len(),
+            max_worst
+        ));
+    } else if opts.all_optimal {
+        let openers = all_optimal_openers(&words, opts.strategy);
+        openers.iter().for_each(|w| println!("{}", word_str(w)));
+        info(format!("{} openers tie for best", openers.len()));
+    } else if opts.explore_exploit {
+        let remaining = remaining(&words, &opts);
+        if remaining.is_empty() {
+            fail(EXIT_NO_CANDIDATES, "No words match the given feedback");
+        }
+        println!("Exploit: {}", best_exploit(&pool, &remaining));
+        println!("Explore: {}", best_explore(&pool, &remaining));
+    } else if opts.distinguish {
+        let remaining = remaining(&words, &opts);
+        if remaining.is_empty() {
+            fail(EXIT_NO_CANDIDATES, "No words match the given feedback");
+        }
+        let g = minimal_distinguisher(&pool, &remaining);
+        println!("{}", explain_guess(&remaining, &g, precision));
+    } else if let Some((before, after)) = &opts.diff_runs {
+        let load = |path: &String| {
+            load_game_results(path)
+                .unwrap_or_else(|e| fail(EXIT_IO, format!("failed to read {}: {}", path, e)))
+        };
+        let changed = diff_runs(&load(before), &load(after));
+        for (w, a, b) in &changed {
+            println!("{} {} -> {}", word_str(w), a, b);
+        }
+        info(format!("{} answers changed", changed.len()));
+    } else if opts.unused_letters {
+        let unused: String = unused_letters(&words, &opts.config).into_iter().collect();
+        if unused.is_empty() {
+            println!("Every letter appears in some word");
+        } else {
+            println!("{}", unused);
+        }
+    } else if opts.runner_up {
+        let remaining = remaining(&words, &opts);
+        match select_with_runner_up(&pool, &remaining, opts.strategy) {
+            Some((best, second, gap)) => println!(
+                "Best: {} Runner-up: {} Gap: {:.p$}",
+                word_str(&best),
+                word_str(&second),
+                gap,
+                p = precision
+            ),
+            None => println!("No runner-up, the list has a single word"),
+        }
+    } else if opts.flexible {
+        let remaining = remaining(&words, &opts);
+        if remaining.is_empty() {
+            fail(EXIT_NO_CANDIDATES, "No words match the given feedback");
+        }
+        let gr = flexibility_guess(&pool, &remaining);
+        if opts.quiet {
+            println!("{}", gr.word_str());
+        } else {
+            println!("Flexible guess: {}", gr);
+            println!("Distribution: {}", format_distribution(&gr.distribution()));
+        }
+    } else if let Some(n) = opts.remaining_guesses {
+        let remaining = remaining(&words, &opts);
+        if remaining.is_empty() {
+            fail(EXIT_NO_CANDIDATES, "No words match the given feedback");
+        }
+        let gr = adaptive_guess(&pool, &remaining, n);
+        if opts.quiet {
+            println!("{}", gr.word_str());
+        } else {
+            println!("Adaptive guess: {}", gr);
+            println!("Distribution: {}", format_distribution(&gr.distribution()));
+        }
+    } else if opts.guaranteed_two {
+        let remaining = remaining(&words, &opts);
+        match find_guaranteed_two(&pool, &remaining) {
+            Some(g) => println!("{} guarantees a win in two", word_str(&g)),
+            None => println!("No guess guarantees a win in two"),
+        }
+    } else if let (true, Some(answer)) = (opts.analyze_line, opts.answer) {
+        println!(
+            "{:.p$}",
+            analyze_line(&words, &answer, &opts.guesses, opts.strategy),
+            p = precision
+        );
+    } else if constrained {
+        // the feedback is already applied, under --duplicates' rules
+        let remaining = remaining(&words, &opts);
+        let gr = match (opts.budget_ms, opts.guess_sample) {
+            (Some(ms), _) => {
+                let budget = Duration::from_millis(ms as u64);
+                best_guess_timed(
+                    &remaining,
+                    &Vec::new(),
+                    opts.strategy,
+                    budget,
+                    opts.branch_cap,
+                )
+            }
+            (None, Some(m)) => {
+                let turn = opts.feedback_rows + opts.guesses.len();
+                let sample = (m, opts.seed.unwrap_or(0));
+                let g = select_guess_sampled(&pool, &remaining, opts.strategy, sample, turn)
+                    .expect("candidates were checked to be there");
+                solve_all(&pool, &remaining, &g, opts.strategy)
+            }
+            (None, None) => best_guess_shared(&remaining, &Vec::new(), opts.branch_cap),
+        };
+        if opts.quiet {
+            println!("{}", gr.word_str());
+        } else {
+            let label = if opts.guess_sample.is_some() && opts.budget_ms.is_none() {
+                "Approximate best guess"
+            } else {
+                "Best guess"
+            };
+            println!("{}: {}", label, gr);
+            println!("Distribution: {}", format_distribution(&gr.distribution()));
+            println!("Partitions: {}", gr.partition_summary());
+        }
+    } else {
+        concise(&words);
+    }
 
     //let res = best_guess(&words[..30].to_vec(), &Vec::new());
     //println!("Result: {:?}", res);
 
     //let mut res = solve(&words[..30].to_vec());
-    //res.sort_by(|a, b| a.guesses.cmp(&b.guesses));
+    //res.sort_by_key(|gr| gr.guesses);
     //println!("{:?}", res);
 
     let elapsed = start.elapsed();
-    println!("Elapsed: {:.2?}", elapsed);
+    info(format!("Elapsed: {:.2?}", elapsed));
 }
 
 // Examples
@@ -266,69 +1067,73 @@ fn concise(words: &Words) {
     let not_used = "chaps";
 
     let facts = factify(&correct, &used, not_used);
-    let gr = best_guess(words, &facts);
-    println!("Best guess: {:?}", gr);
+    let gr = best_guess(words, &facts)
+        .unwrap_or_else(|| fail(EXIT_NO_CANDIDATES, "No words match the example's clues"));
+    println!("Best guess: {}", gr);
+    println!("Distribution: {}", format_distribution(&gr.distribution()));
 }
 
+// an example never run: the facts spelled out one at a time
+#[allow(dead_code, clippy::vec_init_then_push)]
 fn verbose(words: &Words) {
     let mut facts = Vec::new();
     facts.push(Fact {
         letter: 'c',
-        position: 4,
+        position: Some(4),
         feedback: Feedback::Used,
     });
 
     facts.push(Fact {
         letter: 's',
-        position: 4,
+        position: Some(4),
         feedback: Feedback::NotUsed,
     });
 
     facts.push(Fact {
         letter: 't',
-        position: 4,
+        position: Some(4),
         feedback: Feedback::NotUsed,
     });
 
     facts.push(Fact {
         letter: 'o',
-        position: 4,
+        position: Some(4),
         feedback: Feedback::NotUsed,
     });
 
     facts.push(Fact {
         letter: 'i',
-        position: 4,
+        position: Some(4),
         feedback: Feedback::NotUsed,
     });
 
     facts.push(Fact {
         letter: 'd',
-        position: 4,
+        position: Some(4),
         feedback: Feedback::NotUsed,
     });
 
     facts.push(Fact {
         letter: 'u',
-        position: 4,
+        position: Some(4),
         feedback: Feedback::NotUsed,
     });
 
     facts.push(Fact {
         letter: 'm',
-        position: 4,
+        position: Some(4),
         feedback: Feedback::NotUsed,
     });
 
     facts.push(Fact {
         letter: 'p',
-        position: 4,
+        position: Some(4),
         feedback: Feedback::NotUsed,
     });
 
     facts.push(Fact {
         letter: 'y',
-        position: 4,
+        position: Some(4),
         feedback: Feedback::NotUsed,
     });
 
@@ -0,0 +1,339 @@
+// runs the built binary against tests/words.txt, the first 40 answers
+use std::process::{Command, Output};
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_wordle-rust"))
+        .args([
+            "--words",
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/words.txt"),
+        ])
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(out: &Output) -> String {
+    String::from_utf8_lossy(&out.stdout).into_owned()
+}
+
+#[test]
+fn precision_sets_the_decimals_of_averages() {
+    let out = run(&[
+        "--precision",
+        "2",
+        "--validate",
+        "about",
+        "--expect-avg",
+        "1",
+    ]);
+    let text = stdout(&out);
+    let avg = text
+        .split_whitespace()
+        .skip_while(|w| *w != "averages")
+        .nth(1)
+        .unwrap()
+        .trim_end_matches(',');
+    assert_eq!(avg.split_once('.').unwrap().1.len(), 2, "{}", text);
+}
+
+#[test]
+fn an_empty_guess_sample_is_a_usage_error() {
+    let out = run(&["--guess-sample", "0", "--feedback", "fuzzy:BBBBB"]);
+    assert_eq!(out.status.code(), Some(2));
+}
+
+#[test]
+fn absent_letters_dont_count_as_a_turn_for_the_guess_sample() {
+    let sampled = |extra: &[&str]| {
+        let mut args = vec!["--quiet", "--feedback", "fuzzy:BBBBB"];
+        args.extend(extra);
+        args.extend(["--guess-sample", "5", "--seed", "0"]);
+        stdout(&run(&args))
+    };
+    // j, q and z are in none of the words and f and u already gray, so only the
+    // number of facts changes
+    assert_eq!(sampled(&["--absent", "jqzfu"]), sampled(&[]));
+}
+
+#[test]
+fn the_example_without_a_match_is_reported() {
+    // every word of the list starts with an a, which the example rules out
+    let out = run(&[]);
+    assert_eq!(out.status.code(), Some(3));
+}
+
+#[test]
+fn daily_without_words_is_a_usage_error() {
+    let out = run(&["--filter-regex", "zzzzz", "--daily"]);
+    assert_eq!(out.status.code(), Some(2));
+}
+
+#[test]
+fn practice_without_words_is_a_usage_error() {
+    let out = run(&["--filter-regex", "zzzzz", "--practice"]);
+    assert_eq!(out.status.code(), Some(2));
+}
+
+#[test]
+fn closed_keeps_the_suggestions_to_the_answer_list() {
+    let answers =
+        std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/words.txt")).unwrap();
+    let suggested = |extra: &[&str]| {
+        let mut args = vec![
+            "--allowed",
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/data/wordle-allowed-guesses.txt"
+            ),
+            "--interactive",
+        ];
+        args.extend(extra);
+        // "<n> candidates, remaining entropy: <bits> bits, suggestion: <word>"
+        let text = stdout(&run(&args));
+        let line = text.lines().find(|l| l.contains("suggestion: ")).unwrap();
+        line.rsplit(' ').next().unwrap().to_string()
+    };
+    let listed = |w: &str| answers.lines().any(|a| a == w);
+    assert!(!listed(&suggested(&[])));
+    assert!(listed(&suggested(&["--closed"])));
+}
+
+#[test]
+fn a_branch_cap_of_zero_is_a_usage_error() {
+    let out = run(&["--branch-cap", "0", "--feedback", "fuzzy:BBBBB"]);
+    assert_eq!(out.status.code(), Some(2));
+}
+
+#[test]
+fn exact_counts_narrow_the_candidates() {
+    // afoot is the only word of the list with two o's
+    let out = run(&["--exact", "o:2", "--quiet"]);
+    assert_eq!(stdout(&out).trim(), "afoot");
+}
+
+#[test]
+fn a_guess_list_is_ranked_by_the_metric() {
+    let openers = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/openers.txt");
+    let out = run(&["--candidates", openers, "--metric", "expected"]);
+    let text = stdout(&out);
+    // aback is listed first but splits the answers far worse
+    let ranked: Vec<(&str, f64)> = text
+        .lines()
+        .filter_map(|l| {
+            let mut parts = l.split_whitespace();
+            let word = parts.next()?;
+            let expected = parts.skip_while(|p| *p != "Expected:").nth(1)?;
+            Some((word, expected.parse().ok()?))
+        })
+        .collect();
+    assert_eq!(ranked.len(), 2, "{}", text);
+    assert_eq!(ranked[0].0, "adore");
+    assert_eq!(ranked[1].0, "aback");
+    assert!(ranked[0].1 < ranked[1].1);
+}
+
+#[test]
+fn logging_the_search_changes_only_stderr() {
+    // a list where the built in example's facts leave nine words to search
+    let words = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/concise.txt");
+    let run_logged = |log: &str| {
+        Command::new(env!("CARGO_BIN_EXE_wordle-rust"))
+            .args(["--words", words])
+            .env("WORDLE_LOG", log)
+            .output()
+            .unwrap()
+    };
+    let (quiet, logged) = (run_logged("0"), run_logged("1"));
+    let results = |out: &Output| -> Vec<String> {
+        stdout(out)
+            .lines()
+            .filter(|l| !l.starts_with("Elapsed"))
+            .map(String::from)
+            .collect()
+    };
+    assert!(stdout(&quiet).contains("Best guess"));
+    assert_eq!(results(&quiet), results(&logged));
+    assert!(quiet.stderr.is_empty());
+    assert!(String::from_utf8_lossy(&logged.stderr).contains("depth: 0"));
+}
+
+#[test]
+fn resuming_a_partial_checkpoint_finishes_the_solve() {
+    let results = |out: &Output| -> Vec<String> {
+        stdout(out)
+            .lines()
+            .filter(|l| l.starts_with("Word:"))
+            .map(String::from)
+            .collect()
+    };
+    let full = results(&run(&["--solve", "6"]));
+    assert_eq!(full.len(), 6);
+
+    let path = std::env::temp_dir().join("wordle-cli-checkpoint.csv");
+    std::fs::write(&path, "aback,20,6\nabase,11,6\n").unwrap();
+    let out = run(&["--solve", "6", "--resume", path.to_str().unwrap()]);
+    assert_eq!(results(&out), full);
+    let recorded = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(recorded.lines().count(), 6);
+    assert_eq!(
+        recorded.lines().filter(|l| l.starts_with("aback,")).count(),
+        1
+    );
+}
+
+#[test]
+fn exit_codes_tell_the_failures_apart() {
+    let out = run(&["--no-such-flag"]);
+    assert_eq!(out.status.code(), Some(2));
+    assert!(stdout(&out).is_empty());
+    assert_eq!(
+        run(&["--words", "/nonexistent/words.txt"]).status.code(),
+        Some(1)
+    );
+    assert_eq!(
+        run(&["--feedback", "crane:GGGGG", "--quiet"]).status.code(),
+        Some(3)
+    );
+    assert_eq!(
+        run(&["--feedback", "about:GGGGG", "--quiet"]).status.code(),
+        Some(0)
+    );
+}
+
+#[test]
+fn repeated_word_lists_merge_without_duplicates() {
+    // both openers are already in words.txt, none of concise.txt is
+    let dir = env!("CARGO_MANIFEST_DIR");
+    let out = run(&[
+        "--words",
+        &format!("{}/tests/concise.txt", dir),
+        "--words",
+        &format!("{}/tests/openers.txt", dir),
+        "--feedback",
+        "about:GGGGG",
+    ]);
+    let text = stdout(&out);
+    assert_eq!(
+        text.lines().next(),
+        Some("52 words, 50 after removing duplicates"),
+        "{}",
+        text
+    );
+}
+
+#[test]
+fn the_printed_partitions_cover_every_candidate_once() {
+    let out = run(&["--debug-guess", "adore", "--quiet"]);
+    let text = stdout(&out);
+    let mut listed = Vec::new();
+    let mut counted = 0;
+    let mut sizes = Vec::new();
+    for line in text.lines() {
+        let mut fields = line.split_whitespace();
+        fields.next().unwrap();
+        let n: usize = fields.next().unwrap().parse().unwrap();
+        let group: Vec<&str> = fields.collect();
+        assert_eq!(group.len(), n, "{}", line);
+        counted += n;
+        sizes.push(n);
+        listed.extend(group);
+    }
+    assert_eq!(counted, 40);
+    listed.sort_unstable();
+    listed.dedup();
+    assert_eq!(listed.len(), 40);
+    assert!(sizes.windows(2).all(|p| p[0] >= p[1]));
+}
+
+#[test]
+fn a_padded_word_argument_is_trimmed() {
+    let out = run(&["--feedback", " about :GGGGG", "--quiet"]);
+    assert_eq!(out.status.code(), Some(0));
+    assert_eq!(stdout(&out).trim(), "about");
+}
+
+#[test]
+fn a_guess_and_answer_solve_like_their_typed_colors() {
+    let inferred = stdout(&run(&["--guess", "adore", "--answer", "about"]));
+    let typed = stdout(&run(&["--feedback", "adore:GBGBB"]));
+    let mut lines = inferred.lines();
+    assert_eq!(lines.next(), Some("adore GBGBB"));
+    let rest = |lines: std::str::Lines| -> Vec<String> {
+        lines
+            .filter(|l| !l.starts_with("Elapsed"))
+            .map(String::from)
+            .collect()
+    };
+    assert_eq!(rest(lines), rest(typed.lines()));
+}
+
+#[test]
+fn a_word_outside_the_alphabet_is_rejected() {
+    // aback is the only word of the list with a k
+    let out = run(&["--alphabet", "abcdefghijlmnopqrstuvwxyz", "--quiet"]);
+    assert_eq!(out.status.code(), Some(1));
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert_eq!(err.trim(), "words outside the alphabet: aback");
+    let out = run(&[
+        "--alphabet",
+        "abcdefghijklmnopqrstuvwxyz",
+        "--feedback",
+        "fuzzy:BBBBB",
+        "--quiet",
+    ]);
+    assert_eq!(out.status.code(), Some(0));
+}
+
+#[test]
+fn validate_accepts_the_right_average_and_rejects_a_wrong_one() {
+    // about plays out the list in 101 guesses, 2.525 on average
+    let out = run(&["--validate", "about", "--expect-avg", "2.53"]);
+    assert_eq!(out.status.code(), Some(0));
+    assert!(stdout(&out).contains("about averages 2.5250, expected 2.53: ok"));
+    let out = run(&["--validate", "about", "--expect-avg", "2.6"]);
+    assert_eq!(out.status.code(), Some(4));
+    assert!(stdout(&out).contains("expected 2.6: mismatch"));
+}
+
+#[test]
+fn repeated_letter_answers_are_listed() {
+    // counted by hand, each repeats one letter twice
+    let out = run(&["--list-duplicates", "--quiet"]);
+    assert_eq!(
+        stdout(&out).trim(),
+        "2 (14): aback abase abate abbey abbot abyss adage adapt affix afoot again agape agate aging"
+    );
+}
+
+#[test]
+fn jsonl_output_has_a_parsable_line_per_answer() {
+    let out = run(&["--difficulty", "adore", "--format", "jsonl", "--quiet"]);
+    let text = stdout(&out);
+    let mut answers: Vec<String> = text
+        .lines()
+        .map(|l| {
+            // {"answer": "<word>", "guesses": <n>, "outcome": "<outcome>"}
+            let fields: Vec<&str> = l.split('"').collect();
+            assert_eq!(fields.len(), 11, "{}", l);
+            let guesses = fields[6].trim_matches([':', ',', ' ']).parse().unwrap();
+            let r = wordle_rust::GameResult::new(wordle_rust::to_array(fields[3]), guesses);
+            assert_eq!(r.to_json(), l);
+            fields[3].to_string()
+        })
+        .collect();
+    assert_eq!(answers.len(), 40);
+    answers.sort();
+    answers.dedup();
+    assert_eq!(answers.len(), 40);
+}
+
+#[test]
+fn without_words_the_embedded_list_is_used() {
+    // run away from the checkout, so there is no data directory to fall back on
+    let out = Command::new(env!("CARGO_BIN_EXE_wordle-rust"))
+        .current_dir(std::env::temp_dir())
+        .args(["--feedback", "zonal:GGGGG"])
+        .output()
+        .unwrap();
+    assert_eq!(stdout(&out).lines().next(), Some("2315"));
+}